@@ -0,0 +1,124 @@
+use md2letter_convert::{
+    Format, Indent, LintConfig, LintLevel, LintRule, RenderOptions, WarningCode,
+};
+
+pub(crate) const USAGE: &str = "\
+Usage: md2letter [OPTIONS] <FILE>
+       md2letter lint [LINT OPTIONS] <FILE>
+
+Options:
+  --format <FORMAT>  Output format: letter-script (default), html, latex, typst or json
+  --compact          Render everything on a single line
+  --indent <WIDTH>   Indent nested blocks by WIDTH spaces (default: 4)
+  --tabs             Indent nested blocks using tabs
+  --deny-warnings    Treat all warnings as errors
+  --deny <CODE>      Treat warnings with the given code as errors, for example heading-level-jump
+
+Lint options:
+  --allow <RULE>     Do not check the given rule
+  --warn <RULE>      Report violations of the given rule as warnings (default)
+  --deny <RULE>      Report violations of the given rule as errors
+  --deny-warnings    Report violations of all rules as errors
+
+Lint rules: heading-increment, list-marker-style, table-column-count, trailing-whitespace,
+image-alt-text, code-block-language";
+
+pub(crate) struct Args {
+    pub file_path: String,
+    pub command: Command,
+}
+
+pub(crate) enum Command {
+    Convert {
+        format: Format,
+        render_options: RenderOptions,
+        deny_all_warnings: bool,
+        denied_warnings: Vec<WarningCode>,
+    },
+    Lint {
+        config: LintConfig,
+    },
+}
+
+impl Args {
+    pub(crate) fn parse(args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut args = args.peekable();
+
+        if args.next_if(|arg| arg == "lint").is_some() {
+            Self::parse_lint(args)
+        } else {
+            Self::parse_convert(args)
+        }
+    }
+
+    fn parse_convert(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut file_path = None;
+        let mut format = Format::default();
+        let mut render_options = RenderOptions::default();
+        let mut deny_all_warnings = false;
+        let mut denied_warnings = Vec::new();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" => format = next_value(&mut args, &arg)?.parse()?,
+                "--compact" => render_options = RenderOptions::compact(),
+                "--tabs" => render_options = RenderOptions::pretty(Indent::Tabs),
+                "--indent" => {
+                    let width = next_value(&mut args, &arg)?;
+                    let width = width
+                        .parse::<usize>()
+                        .map_err(|_| format!("Invalid indent width '{}'", width))?;
+
+                    render_options = RenderOptions::pretty(Indent::Spaces(width));
+                }
+                "--deny-warnings" => deny_all_warnings = true,
+                "--deny" => denied_warnings.push(next_value(&mut args, &arg)?.parse()?),
+                _ if arg.starts_with("--") => return Err(format!("Unknown option '{}'", arg)),
+                _ => file_path = Some(arg),
+            }
+        }
+
+        Ok(Self {
+            file_path: file_path.ok_or_else(|| "Missing input file".to_string())?,
+            command: Command::Convert {
+                format,
+                render_options,
+                deny_all_warnings,
+                denied_warnings,
+            },
+        })
+    }
+
+    fn parse_lint(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut file_path = None;
+        let mut default_level = LintLevel::Warn;
+        let mut levels: Vec<(LintRule, LintLevel)> = Vec::new();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--allow" => levels.push((next_value(&mut args, &arg)?.parse()?, LintLevel::Allow)),
+                "--warn" => levels.push((next_value(&mut args, &arg)?.parse()?, LintLevel::Warn)),
+                "--deny" => levels.push((next_value(&mut args, &arg)?.parse()?, LintLevel::Deny)),
+                "--deny-warnings" => default_level = LintLevel::Deny,
+                _ if arg.starts_with("--") => return Err(format!("Unknown option '{}'", arg)),
+                _ => file_path = Some(arg),
+            }
+        }
+
+        let config = levels
+            .into_iter()
+            .fold(LintConfig::new(default_level), |config, (rule, level)| {
+                config.with_level(rule, level)
+            });
+
+        Ok(Self {
+            file_path: file_path.ok_or_else(|| "Missing input file".to_string())?,
+            command: Command::Lint { config },
+        })
+    }
+}
+
+fn next_value(args: &mut impl Iterator<Item = String>, option: &str) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("Missing value for '{}'", option))
+}
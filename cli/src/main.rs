@@ -1,20 +1,62 @@
-use md2letter_convert::convert;
 use std::env;
 use std::error::Error;
-use std::fs::File;
-use std::io::BufReader;
+use std::fs;
+use std::io::Cursor;
+use std::process;
+
+use md2letter_convert::{convert_with_recovery, lint, render_diagnostic, Severity};
+
+use crate::args::{Args, Command, USAGE};
+
+mod args;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let source = fs::read(&args.file_path)?;
+    let reader = Cursor::new(source.clone());
 
-    let file_path = &args[1];
+    let (result, diagnostics) = match args.command {
+        Command::Convert {
+            format,
+            render_options,
+            deny_all_warnings,
+            denied_warnings,
+        } => match convert_with_recovery(Box::new(reader), format, &render_options) {
+            Ok(recovered) if deny_all_warnings => recovered.deny_all_warnings().into_parts(),
+            Ok(recovered) => recovered.deny_warnings(&denied_warnings).into_parts(),
+            Err(error) => (String::new(), vec![error.to_diagnostic()]),
+        },
+        Command::Lint { config } => match lint(Box::new(reader), &config) {
+            Ok(diagnostics) => (String::new(), diagnostics),
+            Err(error) => (String::new(), vec![error.to_diagnostic()]),
+        },
+    };
 
-    let file = File::open(file_path)?;
-    let mut reader = BufReader::new(file);
+    let source = String::from_utf8_lossy(&source);
+    for diagnostic in &diagnostics {
+        eprintln!(
+            "{}",
+            render_diagnostic(diagnostic, &args.file_path, &source)
+        );
+    }
 
-    let result = convert(Box::new(reader))?;
+    if !result.is_empty() {
+        println!("{}", result.trim_end_matches('\n'));
+    }
 
-    println!("{}", result);
+    if diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity() == Severity::Error)
+    {
+        process::exit(1);
+    }
 
     Ok(())
 }
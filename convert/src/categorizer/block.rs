@@ -1,7 +1,12 @@
+use std::fmt;
+
 use crate::util::SourceSpan;
+use crate::warning::Warning;
 
-#[derive(Debug, PartialEq, Eq)]
-pub(crate) enum BlockKind {
+/// Kind of a Markdown block determining the parser used for it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BlockKind {
     Text,
     Heading,
     List,
@@ -11,6 +16,28 @@ pub(crate) enum BlockKind {
     Code,
     Function,
     HorizontalRule,
+    Footnote,
+    LinkReference,
+}
+
+impl fmt::Display for BlockKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BlockKind::Text => "text",
+            BlockKind::Heading => "heading",
+            BlockKind::List => "list",
+            BlockKind::Table => "table",
+            BlockKind::Image => "image",
+            BlockKind::Quote => "quote",
+            BlockKind::Code => "code",
+            BlockKind::Function => "function",
+            BlockKind::HorizontalRule => "horizontal rule",
+            BlockKind::Footnote => "footnote definition",
+            BlockKind::LinkReference => "link reference definition",
+        };
+
+        write!(f, "{}", name)
+    }
 }
 
 #[derive(Debug)]
@@ -18,25 +45,43 @@ pub(crate) struct CategorizedBlock {
     kind: BlockKind,
     src: String,
     span: SourceSpan,
+    warnings: Vec<Warning>,
 }
 
 impl CategorizedBlock {
     pub(crate) fn new(kind: BlockKind, src: String, span: SourceSpan) -> Self {
-        Self { kind, src, span }
+        Self {
+            kind,
+            src,
+            span,
+            warnings: Vec::new(),
+        }
+    }
+
+    pub(crate) fn with_warning(mut self, warning: Warning) -> Self {
+        self.warnings.push(warning);
+        self
     }
 
     pub(crate) fn kind(&self) -> &BlockKind {
         &self.kind
     }
 
+    #[cfg(test)]
     pub(crate) fn src(&self) -> &str {
         &self.src
     }
 
+    #[cfg(test)]
     pub(crate) fn span(&self) -> &SourceSpan {
         &self.span
     }
 
+    /// Take the warnings found while categorizing the block.
+    pub(crate) fn take_warnings(&mut self) -> Vec<Warning> {
+        std::mem::take(&mut self.warnings)
+    }
+
     pub(crate) fn consume(self) -> (BlockKind, String, SourceSpan) {
         (self.kind, self.src, self.span)
     }
@@ -5,11 +5,18 @@
 //! The categorization thus is just a performance optimization.
 //! If the categorization is wrong - meaning that the designated parser is not able to figure out what the content means - the text parser is used as a fallback.
 
+pub use crate::categorizer::block::BlockKind;
 use crate::categorizer::block::BlockKind::{
-    Code, Function, Heading, HorizontalRule, Image, List, Quote, Table, Text,
+    Code, Footnote, Function, Heading, HorizontalRule, Image, LinkReference, List, Quote, Table,
+    Text,
 };
-pub(crate) use crate::categorizer::block::{BlockKind, CategorizedBlock};
+pub(crate) use crate::categorizer::block::CategorizedBlock;
 use crate::splitter::SplitterBlock;
+use crate::util::{
+    indentation_width, is_delimiter_cell, split_link_destination, split_table_row,
+    CODE_INDENT_WIDTH,
+};
+use crate::warning::{Warning, WarningCode};
 
 pub(crate) struct BlockCategorizer;
 
@@ -83,10 +90,26 @@ impl BlockCategorizer {
                     Text
                 }
             }
+            '[' => {
+                if self.is_footnote_definition(&src) {
+                    Footnote
+                } else if self.is_link_reference_definition(&src) {
+                    LinkReference
+                } else {
+                    Text
+                }
+            }
             '>' => Quote,
             '|' => Table,
-            '`' => {
-                if self.is_code_block(&src) {
+            '`' | '~' => {
+                if self.is_code_block(&src, first_char) {
+                    Code
+                } else {
+                    Text
+                }
+            }
+            ' ' | '\t' => {
+                if self.is_indented_code_block(&src) || self.is_indented_fence(&src) {
                     Code
                 } else {
                     Text
@@ -94,15 +117,52 @@ impl BlockCategorizer {
             }
             _ => Text,
         };
+        let kind = if kind == Text && self.is_table(&src) {
+            Table
+        } else if kind == Text && self.is_setext_heading(&src) {
+            Heading
+        } else {
+            kind
+        };
+
+        let has_stray_function_text =
+            kind == Text && first_char == '#' && self.has_stray_function_text(&src);
+
+        let block = CategorizedBlock::new(kind, src, source_span.clone());
+        if has_stray_function_text {
+            block.with_warning(Warning::new(
+                WarningCode::StrayFunctionText,
+                "Function call is followed by more text and is thus treated as text",
+                source_span,
+            ))
+        } else {
+            block
+        }
+    }
+
+    /// Check whether the block is a table without leading pipes,
+    /// which requires a delimiter row with as many cells as the header row.
+    fn is_table(&self, src: &str) -> bool {
+        let mut lines = src.lines();
+
+        match (lines.next(), lines.next()) {
+            (Some(header_row), Some(delimiter_row)) if delimiter_row.contains('|') => {
+                let delimiter_cells = split_table_row(delimiter_row);
 
-        CategorizedBlock::new(kind, src, source_span)
+                delimiter_cells
+                    .iter()
+                    .all(|cell| is_delimiter_cell(cell.value()))
+                    && delimiter_cells.len() == split_table_row(header_row).len()
+            }
+            _ => false,
+        }
     }
 
-    fn is_code_block(&self, src: &str) -> bool {
+    fn is_code_block(&self, src: &str, fence_char: char) -> bool {
         let mut counter = 0;
 
         for c in src.chars() {
-            if c == '`' {
+            if c == fence_char {
                 counter += 1;
             } else {
                 break;
@@ -112,31 +172,36 @@ impl BlockCategorizer {
         counter >= 3
     }
 
+    /// Check whether the block starts with a code fence indented by up to three spaces.
+    fn is_indented_fence(&self, src: &str) -> bool {
+        let trimmed_src = src.trim_start_matches(' ');
+
+        match trimmed_src.chars().next() {
+            Some(fence_char @ ('`' | '~')) => {
+                indentation_width(src) < CODE_INDENT_WIDTH
+                    && self.is_code_block(trimmed_src, fence_char)
+            }
+            _ => false,
+        }
+    }
+
+    /// Check whether all lines of the block are indented enough to form an indented code block.
+    fn is_indented_code_block(&self, src: &str) -> bool {
+        src.lines()
+            .filter(|line| !line.trim().is_empty())
+            .all(|line| indentation_width(line) >= CODE_INDENT_WIDTH)
+    }
+
     fn is_ordered_list(&self, src: &str) -> bool {
-        let next_char_is_period = src
-            .chars()
-            .skip(1)
-            .next()
-            .map(|c| c == '.')
-            .unwrap_or(false);
-        let next_char_is_space = next_char_is_period
-            && src
-                .chars()
-                .skip(2)
-                .next()
-                .map(|c| c == ' ')
-                .unwrap_or(false);
+        let next_char_is_period = src.chars().nth(1).map(|c| c == '.').unwrap_or(false);
+        let next_char_is_space =
+            next_char_is_period && src.chars().nth(2).map(|c| c == ' ').unwrap_or(false);
 
         next_char_is_period && next_char_is_space
     }
 
     fn is_unordered_list(&self, src: &str, _char: char) -> bool {
-        let next_char_is_space = src
-            .chars()
-            .skip(1)
-            .next()
-            .map(|c| c == ' ')
-            .unwrap_or(false);
+        let next_char_is_space = src.chars().nth(1).map(|c| c == ' ').unwrap_or(false);
 
         next_char_is_space
     }
@@ -168,7 +233,7 @@ impl BlockCategorizer {
     }
 
     fn is_image(&self, src: &str) -> bool {
-        if let Some(c) = src.chars().skip(1).next() {
+        if let Some(c) = src.chars().nth(1) {
             if c != '[' {
                 return false;
             }
@@ -215,10 +280,28 @@ impl BlockCategorizer {
             }
         }
 
-        return may_be_image_block;
+        may_be_image_block
     }
 
     fn is_function_block(&self, src: &str) -> bool {
+        match self.find_function_call_end(src) {
+            Some((end, _)) => self.is_blank_from(src, end),
+            None => false,
+        }
+    }
+
+    /// Check whether the block starts with a function call with parameters
+    /// that is followed by more content, making it a text block instead.
+    fn has_stray_function_text(&self, src: &str) -> bool {
+        match self.find_function_call_end(src) {
+            Some((end, true)) => !self.is_blank_from(src, end),
+            _ => false,
+        }
+    }
+
+    /// Find the char offset after the function call at the start of the block
+    /// and whether the call has parameters.
+    fn find_function_call_end(&self, src: &str) -> Option<(usize, bool)> {
         let mut counter = 1;
 
         // Find function name
@@ -234,15 +317,15 @@ impl BlockCategorizer {
                         break;
                     }
 
-                    return false;
+                    return None;
                 }
-                '\t' | '#' => return false,
+                '\t' | '#' => return None,
                 ' ' => {
                     if has_name {
                         // May be function without parameters like '#break'
                         break;
                     } else {
-                        return false;
+                        return None;
                     }
                 }
                 _ => {
@@ -265,18 +348,47 @@ impl BlockCategorizer {
         }
 
         if !params_are_valid {
-            return false;
+            return None;
         }
 
-        // Check if there is more content to the block than just the function
-        for c in src.chars().skip(counter) {
-            match c {
-                ' ' | '\t' | '\n' => {}
-                _ => return false,
-            }
+        Some((counter, anticipate_params))
+    }
+
+    /// Check if there is no more content in the block after the given char offset.
+    fn is_blank_from(&self, src: &str, offset: usize) -> bool {
+        src.chars()
+            .skip(offset)
+            .all(|c| matches!(c, ' ' | '\t' | '\n'))
+    }
+
+    fn is_footnote_definition(&self, src: &str) -> bool {
+        match src
+            .strip_prefix("[^")
+            .and_then(|rest| rest.split_once("]:"))
+        {
+            Some((label, _)) => is_footnote_label(label),
+            None => false,
         }
+    }
 
-        return true;
+    /// Check whether every line of the block is a link reference definition
+    /// whose target and optional title are valid, like `[label]: https://example.com "Title"`.
+    fn is_link_reference_definition(&self, src: &str) -> bool {
+        src.lines().all(|line| {
+            split_link_reference_definition(line)
+                .and_then(|(_, rest)| split_link_destination(rest))
+                .is_some()
+        })
+    }
+
+    /// Check whether the block is a heading underlined by `===` or `---` on its last line.
+    fn is_setext_heading(&self, src: &str) -> bool {
+        match src.trim_end().rsplit_once('\n') {
+            Some((content, underline)) => {
+                !content.trim().is_empty() && setext_heading_level(underline).is_some()
+            }
+            None => false,
+        }
     }
 
     fn is_heading(&self, src: &str) -> bool {
@@ -292,13 +404,47 @@ impl BlockCategorizer {
             }
         }
 
-        return false;
+        false
+    }
+}
+
+/// Check whether the given label may be used for a footnote like `[^label]`.
+pub(crate) fn is_footnote_label(label: &str) -> bool {
+    !label.is_empty() && !label.contains(|c: char| c.is_whitespace() || c == ']' || c == '[')
+}
+
+/// Split a link reference definition like `[label]: https://example.com "Title"`
+/// into the label and the rest of the line following the colon.
+pub(crate) fn split_link_reference_definition(line: &str) -> Option<(&str, &str)> {
+    let (label, rest) = line.strip_prefix('[')?.split_once("]:")?;
+    let rest = rest.trim();
+
+    let is_valid_label =
+        !label.trim().is_empty() && !label.starts_with('^') && !label.contains('[');
+    if is_valid_label && !rest.is_empty() {
+        Some((label, rest))
+    } else {
+        None
+    }
+}
+
+/// Get the heading level of a setext heading underline, which is 1 for `===` and 2 for `---`.
+pub(crate) fn setext_heading_level(line: &str) -> Option<usize> {
+    let line = line.trim();
+    if line.is_empty() {
+        None
+    } else if line.chars().all(|c| c == '=') {
+        Some(1)
+    } else if line.chars().all(|c| c == '-') {
+        Some(2)
+    } else {
+        None
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::categorizer::block::BlockKind::{Code, HorizontalRule, List, Quote, Table};
+    use crate::categorizer::block::BlockKind::{Code, HorizontalRule, List, Quote, Table, Text};
     use crate::util::SourcePosition;
     use crate::util::SourceSpan;
 
@@ -353,6 +499,50 @@ console.log('test');
         );
     }
 
+    #[test]
+    fn categorize_tilde_and_indented_code() {
+        let categorizer = BlockCategorizer::new();
+
+        for src in [
+            "~~~\ncode\n~~~",
+            "````\n```\n````",
+            "    code\n\n\tmore code",
+            " ```\ncode\n```",
+            "   ~~~\n   code\n   ~~~",
+        ] {
+            let block = SplitterBlock::new(
+                src.to_string(),
+                SourceSpan::new(SourcePosition::zero(), SourcePosition::zero()),
+            );
+
+            assert_eq!(categorizer.categorize(block).kind(), &Code, "{}", src);
+        }
+    }
+
+    #[test]
+    fn categorize_partially_indented_block_as_text() {
+        let block = SplitterBlock::new(
+            "    code\nnot code".to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(2, 9)),
+        );
+
+        let categorizer = BlockCategorizer::new();
+
+        assert_eq!(categorizer.categorize(block).kind(), &Text);
+    }
+
+    #[test]
+    fn categorize_fence_indented_by_four_spaces_as_text() {
+        let block = SplitterBlock::new(
+            "    ```\ncode\n```".to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(3, 4)),
+        );
+
+        let categorizer = BlockCategorizer::new();
+
+        assert_eq!(categorizer.categorize(block).kind(), &Text);
+    }
+
     #[test]
     fn categorize_faulty_code_as_text() {
         let code_block = SplitterBlock::new(
@@ -399,6 +589,23 @@ console.log('test');
         );
     }
 
+    #[test]
+    fn categorize_table_without_leading_pipes() {
+        let categorizer = BlockCategorizer::new();
+
+        let table_block = SplitterBlock::new(
+            "Name | Age\n:--- | ---\nMax | 42".to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(3, 9)),
+        );
+        assert_eq!(categorizer.categorize(table_block).kind(), &Table);
+
+        let text_block = SplitterBlock::new(
+            "Either this | or that\n- - -".to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(2, 6)),
+        );
+        assert_eq!(categorizer.categorize(text_block).kind(), &Text);
+    }
+
     #[test]
     fn categorize_table() {
         let table_block = SplitterBlock::new(
@@ -970,4 +1177,143 @@ console.log('test');
             &SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 7))
         );
     }
+
+    #[test]
+    fn categorize_function_with_stray_text_as_text_with_warning() {
+        let block = SplitterBlock::new(
+            "#image(src: cat.png) is a cat".to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 30)),
+        );
+
+        let categorizer = BlockCategorizer::new();
+
+        let mut categorized_block = categorizer.categorize(block);
+
+        assert_eq!(categorized_block.kind(), &Text);
+        let warnings = categorized_block.take_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code(), WarningCode::StrayFunctionText);
+    }
+
+    #[test]
+    fn categorize_hashtag_text_without_warning() {
+        let block = SplitterBlock::new(
+            "#hashtag is not a function".to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 27)),
+        );
+
+        let categorizer = BlockCategorizer::new();
+
+        let mut categorized_block = categorizer.categorize(block);
+
+        assert_eq!(categorized_block.kind(), &Text);
+        assert!(categorized_block.take_warnings().is_empty());
+    }
+
+    #[test]
+    fn categorize_footnote_definition() {
+        let block = SplitterBlock::new(
+            "[^1]: A footnote.\n[^note]: Another footnote.".to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(2, 26)),
+        );
+
+        let categorizer = BlockCategorizer::new();
+
+        let categorized_block = categorizer.categorize(block);
+
+        assert_eq!(categorized_block.kind(), &Footnote);
+    }
+
+    #[test]
+    fn categorize_setext_headings() {
+        let categorizer = BlockCategorizer::new();
+
+        for src in [
+            "Title\n=====",
+            "Subtitle\n---",
+            "Multi-line\ntitle\n=",
+            "*Emphasized*\n---",
+        ] {
+            let block = SplitterBlock::new(
+                src.to_string(),
+                SourceSpan::new(SourcePosition::zero(), SourcePosition::zero()),
+            );
+
+            assert_eq!(categorizer.categorize(block).kind(), &Heading, "{}", src);
+        }
+    }
+
+    #[test]
+    fn categorize_lone_underline_as_horizontal_rule() {
+        let block = SplitterBlock::new(
+            "---".to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 4)),
+        );
+
+        let categorizer = BlockCategorizer::new();
+
+        assert_eq!(categorizer.categorize(block).kind(), &HorizontalRule);
+    }
+
+    #[test]
+    fn categorize_text_with_dashes_as_text() {
+        let block = SplitterBlock::new(
+            "Some text\n--- and more".to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(2, 13)),
+        );
+
+        let categorizer = BlockCategorizer::new();
+
+        assert_eq!(categorizer.categorize(block).kind(), &Text);
+    }
+
+    #[test]
+    fn categorize_link_reference_definitions() {
+        let block = SplitterBlock::new(
+            "[docs]: https://example.com/docs \"Docs\"\n[Other Page]: <other.html>".to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(2, 26)),
+        );
+
+        let categorizer = BlockCategorizer::new();
+
+        assert_eq!(categorizer.categorize(block).kind(), &LinkReference);
+    }
+
+    #[test]
+    fn categorize_link_reference_followed_by_text_as_text() {
+        let block = SplitterBlock::new(
+            "[docs]: https://example.com/docs\nSome text".to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(2, 10)),
+        );
+
+        let categorizer = BlockCategorizer::new();
+
+        assert_eq!(categorizer.categorize(block).kind(), &Text);
+    }
+
+    #[test]
+    fn categorize_prose_looking_like_link_reference_as_text() {
+        let block = SplitterBlock::new(
+            "[Note]: remember to bring snacks".to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 33)),
+        );
+
+        let categorizer = BlockCategorizer::new();
+
+        assert_eq!(categorizer.categorize(block).kind(), &Text);
+    }
+
+    #[test]
+    fn categorize_link_as_text() {
+        let block = SplitterBlock::new(
+            "[Link](https://example.com): not a footnote".to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 44)),
+        );
+
+        let categorizer = BlockCategorizer::new();
+
+        let categorized_block = categorizer.categorize(block);
+
+        assert_eq!(categorized_block.kind(), &Text);
+    }
 }
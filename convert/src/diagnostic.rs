@@ -0,0 +1,264 @@
+//! Diagnostics pointing to a location in the Markdown source, rendered similar to rustc.
+
+use std::fmt;
+
+use crate::util::SourceSpan;
+
+/// How severe a diagnostic is.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem found in the Markdown source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    severity: Severity,
+    code: Option<String>,
+    message: String,
+    span: Option<SourceSpan>,
+    help: Option<String>,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            code: None,
+            message: message.into(),
+            span: None,
+            help: None,
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    /// Identify the kind of problem, for example the code of a warning.
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Treat the diagnostic as an error, for example to deny a warning.
+    pub fn into_error(mut self) -> Self {
+        self.severity = Severity::Error;
+        self
+    }
+
+    /// Point the diagnostic to the given span in the source.
+    pub fn with_span(mut self, span: SourceSpan) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Add a hint on how to fix the problem.
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn span(&self) -> Option<&SourceSpan> {
+        self.span.as_ref()
+    }
+
+    pub fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+}
+
+/// Render the diagnostic for the given source file similar to how rustc does it:
+///
+/// ```text
+/// error: Expected closing parenthesis for function parameters
+///  --> example.md:3:1
+///   |
+/// 3 | #image(src: image.png
+///   | ^^^^^^^^^^^^^^^^^^^^^
+///   |
+///   = help: Add ')' at the end of the function parameters
+/// ```
+pub fn render_diagnostic(diagnostic: &Diagnostic, file_name: &str, source: &str) -> String {
+    let mut result = match diagnostic.code() {
+        Some(code) => format!(
+            "{}[{}]: {}\n",
+            diagnostic.severity(),
+            code,
+            diagnostic.message()
+        ),
+        None => format!("{}: {}\n", diagnostic.severity(), diagnostic.message()),
+    };
+
+    let span = match diagnostic.span() {
+        Some(span) => span,
+        None => {
+            if let Some(help) = diagnostic.help() {
+                result.push_str(&format!("  = help: {}\n", help));
+            }
+            return result;
+        }
+    };
+
+    let line_number = span.start.line;
+    let gutter = " ".repeat(line_number.to_string().len());
+    let line = source
+        .lines()
+        .nth(line_number.saturating_sub(1))
+        .unwrap_or("");
+
+    result.push_str(&format!(
+        "{}--> {}:{}:{}\n",
+        gutter, file_name, line_number, span.start.column
+    ));
+    result.push_str(&format!("{} |\n", gutter));
+    result.push_str(&format!("{} | {}\n", line_number, line));
+    result.push_str(&format!("{} | {}\n", gutter, underline(line, span)));
+
+    if let Some(help) = diagnostic.help() {
+        result.push_str(&format!("{} |\n", gutter));
+        result.push_str(&format!("{} = help: {}\n", gutter, help));
+    }
+
+    result
+}
+
+/// Underline the part of the given line covered by the span.
+/// Spans reaching beyond the line are underlined until the end of the line.
+fn underline(line: &str, span: &SourceSpan) -> String {
+    let start = span.start.column.saturating_sub(1);
+    let line_length = line.chars().count();
+    let end = if span.end.line == span.start.line {
+        span.end.column.saturating_sub(1).min(line_length)
+    } else {
+        line_length
+    };
+    let length = end.saturating_sub(start).max(1);
+
+    // Keep tabs of the source line so that the carets line up
+    let mut result = line
+        .chars()
+        .chain(std::iter::repeat(' '))
+        .take(start)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect::<String>();
+    result.push_str(&"^".repeat(length));
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::SourcePosition;
+
+    use super::*;
+
+    fn span(start: (usize, usize), end: (usize, usize)) -> SourceSpan {
+        SourceSpan::new(
+            SourcePosition::new(start.0, start.1),
+            SourcePosition::new(end.0, end.1),
+        )
+    }
+
+    #[test]
+    fn should_render_diagnostic_with_source_line_and_help() {
+        let diagnostic = Diagnostic::error("Expected closing parenthesis for function parameters")
+            .with_span(span((3, 1), (3, 22)))
+            .with_help("Add ')' at the end of the function parameters");
+        let source = "# Title\n\n#image(src: image.png\n";
+
+        assert_eq!(
+            render_diagnostic(&diagnostic, "example.md", source),
+            "\
+error: Expected closing parenthesis for function parameters
+ --> example.md:3:1
+  |
+3 | #image(src: image.png
+  | ^^^^^^^^^^^^^^^^^^^^^
+  |
+  = help: Add ')' at the end of the function parameters
+"
+        );
+    }
+
+    #[test]
+    fn should_render_single_caret_for_position() {
+        let diagnostic = Diagnostic::warning("Something odd").with_span(span((10, 3), (10, 3)));
+        let source = "\n".repeat(9) + "\tab cd\n";
+
+        assert_eq!(
+            render_diagnostic(&diagnostic, "example.md", &source),
+            "\
+warning: Something odd
+  --> example.md:10:3
+   |
+10 | \tab cd
+   | \t ^
+"
+        );
+    }
+
+    #[test]
+    fn should_render_diagnostic_without_span() {
+        let diagnostic = Diagnostic::error("Failed to read").with_help("Check the file");
+
+        assert_eq!(
+            render_diagnostic(&diagnostic, "example.md", ""),
+            "error: Failed to read\n  = help: Check the file\n"
+        );
+    }
+
+    #[test]
+    fn should_render_diagnostic_code() {
+        let diagnostic = Diagnostic::warning("Image has no alternative text")
+            .with_code("empty-image-alt")
+            .with_span(span((1, 1), (1, 13)));
+
+        assert_eq!(
+            render_diagnostic(&diagnostic, "example.md", "![](cat.png)\n"),
+            "\
+warning[empty-image-alt]: Image has no alternative text
+ --> example.md:1:1
+  |
+1 | ![](cat.png)
+  | ^^^^^^^^^^^^
+"
+        );
+    }
+
+    #[test]
+    fn should_keep_code_when_promoting_to_error() {
+        let diagnostic = Diagnostic::warning("Something odd")
+            .with_code("odd")
+            .into_error();
+
+        assert_eq!(diagnostic.severity(), Severity::Error);
+        assert_eq!(diagnostic.code(), Some("odd"));
+    }
+}
@@ -0,0 +1,191 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crate::categorizer::BlockKind;
+use crate::diagnostic::Diagnostic;
+use crate::util::{SourcePosition, SourceSpan};
+
+/// Error occurring while converting a Markdown document.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ConvertError {
+    /// Reading the Markdown source failed.
+    Io(io::Error),
+
+    /// A block of the Markdown source could not be parsed.
+    Parse {
+        position: SourcePosition,
+        span: Option<SourceSpan>,
+        block_kind: BlockKind,
+        message: String,
+        help: Option<String>,
+    },
+
+    /// The parsed blocks could not be transformed to the Letter document model.
+    Transform {
+        span: SourceSpan,
+        message: String,
+        help: Option<String>,
+    },
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConvertError::Io(e) => write!(f, "Failed to read the Markdown source: {}", e),
+            ConvertError::Parse {
+                position,
+                block_kind,
+                message,
+                ..
+            } => write!(
+                f,
+                "Failed to parse {} block at line {}, column {}: {}",
+                block_kind, position.line, position.column, message
+            ),
+            ConvertError::Transform { span, message, .. } => write!(
+                f,
+                "Failed to transform the document at line {}, column {}: {}",
+                span.start.line, span.start.column, message
+            ),
+        }
+    }
+}
+
+impl ConvertError {
+    /// Describe the error as diagnostic pointing to the problematic location in the source (if known).
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        match self {
+            ConvertError::Parse {
+                position,
+                span,
+                block_kind,
+                message,
+                help,
+            } => {
+                let span = span
+                    .clone()
+                    .unwrap_or_else(|| SourceSpan::new(position.clone(), position.clone()));
+                let diagnostic =
+                    Diagnostic::error(format!("{} (in {} block)", message, block_kind))
+                        .with_span(span);
+
+                match help {
+                    Some(help) => diagnostic.with_help(help),
+                    None => diagnostic,
+                }
+            }
+            ConvertError::Transform {
+                span,
+                message,
+                help,
+            } => {
+                let diagnostic = Diagnostic::error(message.clone()).with_span(span.clone());
+
+                match help {
+                    Some(help) => diagnostic.with_help(help),
+                    None => diagnostic,
+                }
+            }
+            _ => Diagnostic::error(self.to_string()),
+        }
+    }
+}
+
+impl Error for ConvertError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConvertError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ConvertError {
+    fn from(e: io::Error) -> Self {
+        ConvertError::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse;
+
+    use super::*;
+
+    #[test]
+    fn should_report_io_error() {
+        let src: &[u8] = &[b'a', 0xff];
+
+        let error = parse(Box::new(src)).unwrap_err();
+
+        assert!(matches!(error, ConvertError::Io(_)));
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn should_report_parse_error_with_block_kind_and_position() {
+        let src = "Some text\n\n```js\nconsole.log('Hello');";
+
+        let error = parse(Box::new(src.as_bytes())).unwrap_err();
+
+        match &error {
+            ConvertError::Parse {
+                position,
+                block_kind,
+                ..
+            } => {
+                assert_eq!(*block_kind, BlockKind::Code);
+                assert_eq!(position.line, 4);
+            }
+            _ => panic!("Expected a parse error, got {:?}", error),
+        }
+        assert!(error
+            .to_string()
+            .starts_with("Failed to parse code block at line 4"));
+    }
+
+    #[test]
+    fn should_display_transform_error() {
+        let error = ConvertError::Transform {
+            span: SourceSpan::new(SourcePosition::new(3, 5), SourcePosition::new(3, 9)),
+            message: "Something went wrong".to_string(),
+            help: None,
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Failed to transform the document at line 3, column 5: Something went wrong"
+        );
+    }
+
+    #[test]
+    fn should_describe_parse_error_as_diagnostic() {
+        let error = ConvertError::Parse {
+            position: SourcePosition::new(2, 3),
+            span: None,
+            block_kind: BlockKind::Quote,
+            message: "Found no quote line start character '>' in line 2".to_string(),
+            help: Some("Start every line of the quote with '>'".to_string()),
+        };
+
+        let diagnostic = error.to_diagnostic();
+
+        assert_eq!(
+            diagnostic.message(),
+            "Found no quote line start character '>' in line 2 (in quote block)"
+        );
+        assert_eq!(
+            diagnostic.span(),
+            Some(&SourceSpan::new(
+                SourcePosition::new(2, 3),
+                SourcePosition::new(2, 3)
+            ))
+        );
+        assert_eq!(
+            diagnostic.help(),
+            Some("Start every line of the quote with '>'")
+        );
+    }
+}
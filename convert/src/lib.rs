@@ -1,43 +1,208 @@
 extern crate core;
 
-use std::error::Error;
-use std::io::Read;
+use std::io::{Cursor, Read};
 
 use parser::BlockParser;
 
+pub use crate::categorizer::BlockKind;
+pub use crate::diagnostic::{render_diagnostic, Diagnostic, Severity};
+pub use crate::error::ConvertError;
+use crate::lint::lint_document;
+pub use crate::lint::{LintConfig, LintLevel, LintRule};
 use crate::parser::{ParseError, ParsedBlock};
-use crate::transformer::transform;
+pub use crate::recovered::Recovered;
+use crate::render::render_tree;
+pub use crate::render::{Format, Indent, Layout, RenderOptions};
+use crate::transformer::{transform, TransformError};
+pub use crate::transformer::{
+    LetterScriptNode, LetterScriptNodeId, LetterScriptNodeKind, LetterScriptTree,
+};
+pub use crate::util::{Alignment, SourcePosition, SourceSpan};
+use crate::warning::Warning;
+pub use crate::warning::WarningCode;
 use crate::{categorizer::BlockCategorizer, splitter::BlockSplitter};
 
 mod categorizer;
+mod diagnostic;
+mod error;
+mod lint;
 mod parser;
+mod recovered;
 mod render;
 mod splitter;
 mod transformer;
 pub(crate) mod util;
+mod warning;
 
-pub type ConvertResult<T> = Result<T, Box<dyn Error>>;
+pub type ConvertResult<T> = Result<T, ConvertError>;
 
+/// The Letter document model of a parsed Markdown document.
+/// It may be inspected and modified before rendering it.
+pub type Document = LetterScriptTree;
+
+/// Convert the Markdown read from the given reader to Letter script.
 pub fn convert(reader: Box<dyn Read>) -> ConvertResult<String> {
-    let splitter = BlockSplitter::new(reader);
+    convert_with_options(reader, Format::default(), &RenderOptions::default())
+}
+
+/// Convert the Markdown read from the given reader to the given format.
+pub fn convert_with_options(
+    reader: Box<dyn Read>,
+    format: Format,
+    options: &RenderOptions,
+) -> ConvertResult<String> {
+    let document = parse(reader)?;
+
+    Ok(render_with_options(&document, format, options))
+}
+
+/// Convert the Markdown read from the given reader to the given format,
+/// rendering blocks that fail to parse as text paragraphs instead of stopping at the first error.
+pub fn convert_with_recovery(
+    reader: Box<dyn Read>,
+    format: Format,
+    options: &RenderOptions,
+) -> ConvertResult<Recovered<String>> {
+    let (document, diagnostics) = parse_with_recovery(reader)?.into_parts();
+
+    Ok(Recovered::new(
+        render_with_options(&document, format, options),
+        diagnostics,
+    ))
+}
+
+/// Parse the Markdown read from the given reader into a document.
+pub fn parse(reader: Box<dyn Read>) -> ConvertResult<Document> {
+    let mut splitter = BlockSplitter::new(reader);
+    let categorizer = BlockCategorizer::new();
+    let parser = BlockParser::new();
+
+    let blocks = splitter
+        .by_ref()
+        .map(|block| categorizer.categorize(block))
+        .map(|categorized_block| {
+            let block_kind = *categorized_block.kind();
+            parser
+                .parse(categorized_block)
+                .map_err(|e| to_convert_error(e, block_kind))
+        })
+        .collect::<ConvertResult<Vec<ParsedBlock>>>();
+
+    // The source may have been cut short by a read error, which is the actual cause of any parse error
+    if let Some(e) = splitter.take_error() {
+        return Err(ConvertError::Io(e));
+    }
+
+    let (document, _, errors) = transform(blocks?.into_iter()).into_parts();
+    if let Some(e) = errors.into_iter().next() {
+        return Err(to_transform_convert_error(e));
+    }
+
+    Ok(document)
+}
+
+/// Parse the Markdown read from the given reader into a document.
+/// Blocks that fail to parse are kept as text paragraphs and reported as error diagnostics,
+/// followed by warnings about suspicious but valid constructs.
+/// Read errors still abort parsing.
+pub fn parse_with_recovery(reader: Box<dyn Read>) -> ConvertResult<Recovered<Document>> {
+    let mut splitter = BlockSplitter::new(reader);
+    let categorizer = BlockCategorizer::new();
+    let parser = BlockParser::new();
+
+    let mut diagnostics = Vec::new();
+    let mut warnings = Vec::new();
+    let blocks = splitter
+        .by_ref()
+        .map(|block| categorizer.categorize(block))
+        .map(|mut categorized_block| {
+            let block_kind = *categorized_block.kind();
+            warnings.extend(categorized_block.take_warnings());
+
+            let (mut parsed_block, error) = parser.parse_with_fallback(categorized_block);
+            if let Some(e) = error {
+                diagnostics.push(to_convert_error(e, block_kind).to_diagnostic());
+            }
+            warnings.extend(parsed_block.take_warnings());
+
+            parsed_block
+        })
+        .collect::<Vec<ParsedBlock>>();
+
+    if let Some(e) = splitter.take_error() {
+        return Err(ConvertError::Io(e));
+    }
+
+    let (document, transform_warnings, transform_errors) =
+        transform(blocks.into_iter()).into_parts();
+    diagnostics.extend(
+        transform_errors
+            .into_iter()
+            .map(|e| to_transform_convert_error(e).to_diagnostic()),
+    );
+    warnings.extend(transform_warnings);
+
+    // Report warnings in source order after the errors
+    warnings.sort_by_key(|warning| (warning.span().start.line, warning.span().start.column));
+    diagnostics.extend(warnings.iter().map(Warning::to_diagnostic));
+
+    Ok(Recovered::new(document, diagnostics))
+}
+
+/// Check the Markdown read from the given reader against the configured style rules.
+/// Blocks that fail to parse are reported as errors, followed by the violations of the lint rules.
+pub fn lint(mut reader: Box<dyn Read>, config: &LintConfig) -> ConvertResult<Vec<Diagnostic>> {
+    // The rules looking at the raw source need it in full, so read it upfront
+    let mut source = String::new();
+    reader.read_to_string(&mut source)?;
+
+    let splitter = BlockSplitter::new(Box::new(Cursor::new(source.clone().into_bytes())));
     let categorizer = BlockCategorizer::new();
     let parser = BlockParser::new();
 
-    let blocks_result: Result<Vec<ParsedBlock>, ParseError> = splitter
-        .into_iter()
+    let mut diagnostics = Vec::new();
+    let blocks = splitter
         .map(|block| categorizer.categorize(block))
-        .map(|categorized_block| parser.parse(categorized_block))
-        .collect();
+        .map(|categorized_block| {
+            let block_kind = *categorized_block.kind();
+            let (parsed_block, error) = parser.parse_with_fallback(categorized_block);
+            if let Some(e) = error {
+                diagnostics.push(to_convert_error(e, block_kind).to_diagnostic());
+            }
 
-    let blocks = if let Ok(blocks) = blocks_result {
-        blocks
-    } else {
-        return Err(format!("Failed to parse block: {:?}", blocks_result).into());
-    };
+            parsed_block
+        })
+        .collect::<Vec<ParsedBlock>>();
 
-    let tree = transform(blocks.into_iter()).map_err(|e| e.message)?;
+    diagnostics.extend(lint_document(&source, &blocks, config));
 
-    // TODO Render tree properly using an XML/HTML formatter
+    Ok(diagnostics)
+}
+
+/// Render the given document in the given format using the default render options.
+pub fn render(document: &Document, format: Format) -> String {
+    render_with_options(document, format, &RenderOptions::default())
+}
+
+/// Render the given document in the given format.
+pub fn render_with_options(document: &Document, format: Format, options: &RenderOptions) -> String {
+    render_tree(document, format, options)
+}
+
+fn to_convert_error(error: ParseError, block_kind: BlockKind) -> ConvertError {
+    ConvertError::Parse {
+        position: error.source_position,
+        span: error.span,
+        block_kind,
+        message: error.message,
+        help: error.help,
+    }
+}
 
-    Ok(tree.to_string())
+fn to_transform_convert_error(error: TransformError) -> ConvertError {
+    ConvertError::Transform {
+        span: error.span,
+        message: error.message,
+        help: error.help,
+    }
 }
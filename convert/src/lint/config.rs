@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use crate::lint::rule::{LintLevel, LintRule};
+
+/// Configures the level of each lint rule.
+/// Rules not configured explicitly use the default level.
+#[derive(Debug, Clone, Default)]
+pub struct LintConfig {
+    default_level: LintLevel,
+    levels: HashMap<LintRule, LintLevel>,
+}
+
+impl LintConfig {
+    pub fn new(default_level: LintLevel) -> Self {
+        Self {
+            default_level,
+            levels: HashMap::new(),
+        }
+    }
+
+    /// Set the level of the given rule.
+    pub fn with_level(mut self, rule: LintRule, level: LintLevel) -> Self {
+        self.set_level(rule, level);
+        self
+    }
+
+    pub fn set_level(&mut self, rule: LintRule, level: LintLevel) {
+        self.levels.insert(rule, level);
+    }
+
+    pub fn level(&self, rule: LintRule) -> LintLevel {
+        self.levels
+            .get(&rule)
+            .copied()
+            .unwrap_or(self.default_level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_warn_about_all_rules_by_default() {
+        let config = LintConfig::default();
+
+        for rule in LintRule::ALL {
+            assert_eq!(config.level(rule), LintLevel::Warn);
+        }
+    }
+
+    #[test]
+    fn should_override_level_of_single_rule() {
+        let config = LintConfig::new(LintLevel::Deny)
+            .with_level(LintRule::TrailingWhitespace, LintLevel::Allow);
+
+        assert_eq!(config.level(LintRule::TrailingWhitespace), LintLevel::Allow);
+        assert_eq!(config.level(LintRule::ImageAltText), LintLevel::Deny);
+    }
+}
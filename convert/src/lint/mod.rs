@@ -0,0 +1,485 @@
+//! Check Markdown documents against configurable style rules.
+
+use std::collections::HashSet;
+
+pub use crate::lint::config::LintConfig;
+pub use crate::lint::rule::{LintLevel, LintRule};
+
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::parser::{
+    ListNodeId, ListNodeKind, ListNodeStyle, ListTree, ParsedBlock, ParsedBlockKind, QuoteNodeId,
+    QuoteNodeKind, QuoteTree, TableRow, TextNodeId, TextNodeKind, TextTree,
+};
+use crate::util::{SourcePosition, SourceSpan};
+
+mod config;
+mod rule;
+
+/// Check the given source and the blocks parsed from it against the configured rules.
+pub(crate) fn lint_document(
+    source: &str,
+    blocks: &[ParsedBlock],
+    config: &LintConfig,
+) -> Vec<Diagnostic> {
+    let mut linter = Linter::new(source, config);
+
+    for block in blocks {
+        linter.lint_block(block);
+    }
+    linter.lint_trailing_whitespace();
+
+    linter.into_diagnostics()
+}
+
+struct Linter<'a> {
+    source: &'a str,
+    config: &'a LintConfig,
+    diagnostics: Vec<Diagnostic>,
+    previous_heading_level: Option<usize>,
+    line_break_lines: HashSet<usize>,
+}
+
+impl<'a> Linter<'a> {
+    fn new(source: &'a str, config: &'a LintConfig) -> Self {
+        Self {
+            source,
+            config,
+            diagnostics: Vec::new(),
+            previous_heading_level: None,
+            line_break_lines: HashSet::new(),
+        }
+    }
+
+    fn into_diagnostics(mut self) -> Vec<Diagnostic> {
+        self.diagnostics.sort_by_key(|diagnostic| {
+            diagnostic
+                .span()
+                .map(|span| (span.start.line, span.start.column))
+        });
+
+        self.diagnostics
+    }
+
+    fn report(&mut self, rule: LintRule, message: String, span: SourceSpan, help: &str) {
+        let severity = match self.config.level(rule) {
+            LintLevel::Allow => return,
+            LintLevel::Warn => Severity::Warning,
+            LintLevel::Deny => Severity::Error,
+        };
+
+        self.diagnostics.push(
+            Diagnostic::new(severity, message)
+                .with_code(rule.name())
+                .with_span(span)
+                .with_help(help),
+        );
+    }
+
+    /// Two trailing spaces are a hard line break when more text of the paragraph follows, thus they are exempt.
+    /// This requires the blocks to be linted before.
+    fn lint_trailing_whitespace(&mut self) {
+        for (index, line) in self.source.lines().enumerate() {
+            let trimmed = line.trim_end_matches([' ', '\t']);
+            let trimmed_length = trimmed.chars().count();
+            let length = line.chars().count();
+
+            let is_line_break =
+                &line[trimmed.len()..] == "  " && self.line_break_lines.contains(&(index + 1));
+            if trimmed_length < length && !is_line_break {
+                self.report(
+                    LintRule::TrailingWhitespace,
+                    "Line ends with whitespace".to_string(),
+                    SourceSpan::new(
+                        SourcePosition::new(index + 1, trimmed_length + 1),
+                        SourcePosition::new(index + 1, length + 1),
+                    ),
+                    "Remove the whitespace at the end of the line",
+                );
+            }
+        }
+    }
+
+    fn lint_block(&mut self, block: &ParsedBlock) {
+        let span = block.span();
+
+        match block.kind() {
+            ParsedBlockKind::Text(text_block) => self.lint_text_tree(text_block.tree()),
+            ParsedBlockKind::Heading(heading_block) => {
+                self.lint_heading_level(heading_block.level(), span);
+                self.lint_text_tree(heading_block.text_tree());
+            }
+            ParsedBlockKind::List(list_block) => {
+                let tree = list_block.tree();
+                self.lint_list_node(tree, tree.root().id());
+            }
+            ParsedBlockKind::Table(table_block) => {
+                let column_count = table_block.header_row().len();
+                self.lint_table_row(table_block.header_row());
+
+                for index in 0..table_block.row_count() {
+                    let row = table_block.get_row(index).unwrap();
+                    // The header row is followed by the delimiter row
+                    let line_number = span.start.line + 2 + index;
+
+                    if row.len() != column_count {
+                        self.report(
+                            LintRule::TableColumnCount,
+                            format!(
+                                "Table row has {} {} while the header row has {}",
+                                row.len(),
+                                if row.len() == 1 { "cell" } else { "cells" },
+                                column_count
+                            ),
+                            self.line_span(line_number),
+                            "Give every row of the table as many cells as the header row",
+                        );
+                    }
+                    self.lint_table_row(row);
+                }
+            }
+            ParsedBlockKind::Image(image_block) => {
+                let text_tree = image_block.text_tree();
+                if plain_text(text_tree, text_tree.root().id())
+                    .trim()
+                    .is_empty()
+                {
+                    self.report_missing_alt_text(span.clone());
+                }
+            }
+            ParsedBlockKind::Quote(quote_block) => {
+                let tree = quote_block.tree();
+                self.lint_quote_node(tree, tree.root().id());
+            }
+            ParsedBlockKind::Code(code_block) => {
+                if code_block.is_fenced() && code_block.language().is_none() {
+                    self.report(
+                        LintRule::CodeBlockLanguage,
+                        "Code block has no language".to_string(),
+                        self.line_span(span.start.line),
+                        "Add the language after the opening fence, for example ```rust",
+                    );
+                }
+            }
+            ParsedBlockKind::Footnote(footnote_block) => {
+                for definition in footnote_block.definitions() {
+                    self.lint_text_tree(definition.text_tree());
+                }
+            }
+            ParsedBlockKind::Function(_)
+            | ParsedBlockKind::HorizontalRule
+            | ParsedBlockKind::LinkReference(_) => {}
+        }
+    }
+
+    fn lint_heading_level(&mut self, level: usize, span: &SourceSpan) {
+        if let Some(previous_level) = self.previous_heading_level {
+            if level > previous_level + 1 {
+                self.report(
+                    LintRule::HeadingIncrement,
+                    format!("Heading level jumps from {} to {}", previous_level, level),
+                    span.clone(),
+                    "Increase heading levels one at a time",
+                );
+            }
+        }
+
+        self.previous_heading_level = Some(level);
+    }
+
+    fn lint_list_node(&mut self, tree: &ListTree, node_id: ListNodeId) {
+        let mut first_symbol: Option<&str> = None;
+
+        for child_id in tree.get_node(node_id).children() {
+            let child = tree.get_node(*child_id);
+
+            match child.kind() {
+                ListNodeKind::Parent => self.lint_list_node(tree, *child_id),
+                ListNodeKind::Leaf {
+                    text_tree, symbol, ..
+                } => {
+                    if let ListNodeStyle::Unordered = child.style() {
+                        match first_symbol {
+                            None => first_symbol = Some(symbol),
+                            Some(first_symbol) if first_symbol != symbol => {
+                                self.report(
+                                    LintRule::ListMarkerStyle,
+                                    format!(
+                                        "List item starts with '{}' while the list started with '{}'",
+                                        symbol, first_symbol
+                                    ),
+                                    text_tree.root().span().clone(),
+                                    &format!("Start all items of the list with '{}'", first_symbol),
+                                );
+                            }
+                            Some(_) => {}
+                        }
+                    }
+
+                    self.lint_text_tree(text_tree);
+                }
+            }
+        }
+    }
+
+    fn lint_quote_node(&mut self, tree: &QuoteTree, node_id: QuoteNodeId) {
+        for child_id in tree.get_node(node_id).children() {
+            match tree.get_node(*child_id).kind() {
+                QuoteNodeKind::Parent => self.lint_quote_node(tree, *child_id),
+                QuoteNodeKind::Leaf { text_tree } => self.lint_text_tree(text_tree),
+            }
+        }
+    }
+
+    fn lint_table_row(&mut self, row: &TableRow) {
+        for cell in row {
+            self.lint_text_tree(cell.text_tree());
+        }
+    }
+
+    /// Check the inline images of the given text tree.
+    fn lint_text_tree(&mut self, tree: &TextTree) {
+        let mut node_ids = vec![tree.root().id()];
+
+        while let Some(node_id) = node_ids.pop() {
+            let node = tree.get_node(node_id);
+
+            match node.kind() {
+                TextNodeKind::Image { .. } | TextNodeKind::ImageReference { .. }
+                    if plain_text(tree, node_id).trim().is_empty() =>
+                {
+                    self.report_missing_alt_text(node.span().clone());
+                }
+                TextNodeKind::LineBreak => {
+                    self.line_break_lines.insert(node.span().start.line);
+                }
+                _ => {}
+            }
+
+            node_ids.extend(node.children());
+        }
+    }
+
+    fn report_missing_alt_text(&mut self, span: SourceSpan) {
+        self.report(
+            LintRule::ImageAltText,
+            "Image has no alternative text".to_string(),
+            span,
+            "Describe the image in between the square brackets, like ![A cat](cat.png)",
+        );
+    }
+
+    /// Span covering the whole line with the given number.
+    fn line_span(&self, line_number: usize) -> SourceSpan {
+        let length = self
+            .source
+            .lines()
+            .nth(line_number - 1)
+            .map(|line| line.chars().count())
+            .unwrap_or(0);
+
+        SourceSpan::new(
+            SourcePosition::new(line_number, 1),
+            SourcePosition::new(line_number, length + 1),
+        )
+    }
+}
+
+fn plain_text(tree: &TextTree, node_id: TextNodeId) -> String {
+    let node = tree.get_node(node_id);
+
+    match node.kind() {
+        TextNodeKind::Text { src } => src.clone(),
+        _ => node
+            .children()
+            .iter()
+            .map(|child_id| plain_text(tree, *child_id))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lint;
+
+    use super::*;
+
+    fn lint_str(src: &'static str, config: &LintConfig) -> Vec<(Option<String>, usize)> {
+        lint(Box::new(src.as_bytes()), config)
+            .unwrap()
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.code().map(str::to_string),
+                    diagnostic.span().unwrap().start.line,
+                )
+            })
+            .collect()
+    }
+
+    fn rule(rule: LintRule, line: usize) -> (Option<String>, usize) {
+        (Some(rule.name().to_string()), line)
+    }
+
+    #[test]
+    fn should_report_skipped_heading_levels() {
+        let src = "\
+# Title
+
+## Subtitle
+
+#### Too deep
+
+# Another title";
+
+        assert_eq!(
+            lint_str(src, &LintConfig::default()),
+            vec![rule(LintRule::HeadingIncrement, 5)]
+        );
+    }
+
+    #[test]
+    fn should_report_mixed_list_markers() {
+        let src = "\
+- One
+- Two
++ Three
+    * Nested
+    * Nested";
+
+        assert_eq!(
+            lint_str(src, &LintConfig::default()),
+            vec![rule(LintRule::ListMarkerStyle, 3)]
+        );
+    }
+
+    #[test]
+    fn should_report_inconsistent_table_column_counts() {
+        let src = "\
+| Name | Age |
+| --- | --- |
+| Alice | 30 |
+| Bob |";
+
+        assert_eq!(
+            lint_str(src, &LintConfig::default()),
+            vec![rule(LintRule::TableColumnCount, 4)]
+        );
+    }
+
+    #[test]
+    fn should_report_trailing_whitespace() {
+        let src = "Some text \nmore text\t\nand the end";
+
+        let diagnostics = lint(Box::new(src.as_bytes()), &LintConfig::default()).unwrap();
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].span(),
+            Some(&SourceSpan::new(
+                SourcePosition::new(1, 10),
+                SourcePosition::new(1, 11)
+            ))
+        );
+        assert_eq!(diagnostics[1].span().unwrap().start.line, 2);
+    }
+
+    #[test]
+    fn should_not_report_two_trailing_spaces_of_hard_line_break() {
+        let src = "\
+Dear Alice,  
+thank you.  
+
+```text
+code  
+```
+
+Three   
+spaces";
+
+        let lines = lint(Box::new(src.as_bytes()), &LintConfig::default())
+            .unwrap()
+            .iter()
+            .map(|diagnostic| diagnostic.span().unwrap().start.line)
+            .collect::<Vec<_>>();
+
+        assert_eq!(lines, vec![2, 5, 8]);
+    }
+
+    #[test]
+    fn should_report_table_row_with_single_cell() {
+        let src = "\
+| Name | Age |
+| --- | --- |
+| Bob |";
+
+        let diagnostics = lint(Box::new(src.as_bytes()), &LintConfig::default()).unwrap();
+
+        assert_eq!(
+            diagnostics[0].message(),
+            "Table row has 1 cell while the header row has 2"
+        );
+    }
+
+    #[test]
+    fn should_report_images_without_alt_text() {
+        let src = "\
+![](cat.png)
+
+Look at ![](dog.png) and ![a bird](bird.png)";
+
+        assert_eq!(
+            lint_str(src, &LintConfig::default()),
+            vec![rule(LintRule::ImageAltText, 1)]
+        );
+    }
+
+    #[test]
+    fn should_report_code_blocks_without_language() {
+        let src = "\
+```
+let x = 1;
+```
+
+```rust
+let y = 2;
+```";
+
+        assert_eq!(
+            lint_str(src, &LintConfig::default()),
+            vec![rule(LintRule::CodeBlockLanguage, 1)]
+        );
+    }
+
+    #[test]
+    fn should_configure_rules_individually() {
+        let src = "# Title\n\n### Subtitle \n\n![](cat.png)";
+        let config = LintConfig::default()
+            .with_level(LintRule::TrailingWhitespace, LintLevel::Allow)
+            .with_level(LintRule::ImageAltText, LintLevel::Deny);
+
+        let diagnostics = lint(Box::new(src.as_bytes()), &config).unwrap();
+
+        let diagnostics = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.code(), diagnostic.severity()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            vec![
+                (Some("heading-increment"), Severity::Warning),
+                (Some("image-alt-text"), Severity::Error),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_parse_errors() {
+        let src = "> A quote\nwithout marker";
+
+        let diagnostics = lint(Box::new(src.as_bytes()), &LintConfig::default()).unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity(), Severity::Error);
+        assert_eq!(diagnostics[0].code(), None);
+    }
+}
@@ -0,0 +1,101 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A Markdown style rule checked by the linter.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LintRule {
+    /// Heading levels must only increase one at a time, like `##` following `#`.
+    HeadingIncrement,
+
+    /// All items of an unordered list must start with the same marker (`-`, `+` or `*`).
+    ListMarkerStyle,
+
+    /// All rows of a table must have as many cells as its header row.
+    TableColumnCount,
+
+    /// Lines must not end with spaces or tabs.
+    TrailingWhitespace,
+
+    /// Images must have an alternative text.
+    ImageAltText,
+
+    /// Code blocks must specify their language.
+    CodeBlockLanguage,
+}
+
+/// How a violation of a lint rule is reported.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum LintLevel {
+    /// Do not check the rule.
+    Allow,
+
+    /// Report violations as warnings.
+    #[default]
+    Warn,
+
+    /// Report violations as errors.
+    Deny,
+}
+
+impl LintRule {
+    /// All lint rules.
+    pub const ALL: [LintRule; 6] = [
+        LintRule::HeadingIncrement,
+        LintRule::ListMarkerStyle,
+        LintRule::TableColumnCount,
+        LintRule::TrailingWhitespace,
+        LintRule::ImageAltText,
+        LintRule::CodeBlockLanguage,
+    ];
+
+    /// Name of the rule as shown in diagnostics and used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LintRule::HeadingIncrement => "heading-increment",
+            LintRule::ListMarkerStyle => "list-marker-style",
+            LintRule::TableColumnCount => "table-column-count",
+            LintRule::TrailingWhitespace => "trailing-whitespace",
+            LintRule::ImageAltText => "image-alt-text",
+            LintRule::CodeBlockLanguage => "code-block-language",
+        }
+    }
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for LintRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LintRule::ALL
+            .iter()
+            .find(|rule| rule.name() == s)
+            .copied()
+            .ok_or_else(|| format!("Unknown lint rule '{}'", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_lint_rule_by_name() {
+        for rule in LintRule::ALL {
+            assert_eq!(rule.name().parse::<LintRule>(), Ok(rule));
+        }
+    }
+
+    #[test]
+    fn should_reject_unknown_lint_rule() {
+        assert_eq!(
+            "no-tabs".parse::<LintRule>(),
+            Err("Unknown lint rule 'no-tabs'".to_string())
+        );
+    }
+}
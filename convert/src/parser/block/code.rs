@@ -1,14 +1,42 @@
+use std::collections::HashMap;
+
 pub(crate) type LanguageIdentifier = String;
 
 #[derive(Debug)]
 pub(crate) struct CodeBlock {
     language: Option<LanguageIdentifier>,
     src: String,
+
+    /// Attributes from the info string following the language, like `title="main.rs"`.
+    attributes: HashMap<String, String>,
+
+    /// Whether the code block is surrounded by fences rather than indented.
+    fenced: bool,
 }
 
 impl CodeBlock {
     pub fn new(language: Option<LanguageIdentifier>, src: String) -> Self {
-        Self { language, src }
+        Self {
+            language,
+            src,
+            attributes: HashMap::new(),
+            fenced: true,
+        }
+    }
+
+    /// Create a code block indented by four spaces, which cannot specify a language.
+    pub fn indented(src: String) -> Self {
+        Self {
+            language: None,
+            src,
+            attributes: HashMap::new(),
+            fenced: false,
+        }
+    }
+
+    pub fn with_attributes(mut self, attributes: HashMap<String, String>) -> Self {
+        self.attributes = attributes;
+        self
     }
 
     pub fn language(&self) -> &Option<LanguageIdentifier> {
@@ -18,4 +46,12 @@ impl CodeBlock {
     pub fn src(&self) -> &str {
         &self.src
     }
+
+    pub fn attributes(&self) -> &HashMap<String, String> {
+        &self.attributes
+    }
+
+    pub fn is_fenced(&self) -> bool {
+        self.fenced
+    }
 }
@@ -0,0 +1,53 @@
+use super::text;
+use crate::util::SourceSpan;
+
+pub(crate) type FootnoteLabel = String;
+
+/// Block of one or more consecutive footnote definitions like `[^1]: Some text`.
+#[derive(Debug)]
+pub(crate) struct FootnoteBlock {
+    definitions: Vec<FootnoteDefinition>,
+}
+
+#[derive(Debug)]
+pub(crate) struct FootnoteDefinition {
+    label: FootnoteLabel,
+    text_tree: text::TextTree,
+    span: SourceSpan,
+}
+
+impl FootnoteBlock {
+    pub fn new(definitions: Vec<FootnoteDefinition>) -> Self {
+        Self { definitions }
+    }
+
+    pub fn definitions(&self) -> &[FootnoteDefinition] {
+        &self.definitions
+    }
+
+    pub fn into_definitions(self) -> Vec<FootnoteDefinition> {
+        self.definitions
+    }
+}
+
+impl FootnoteDefinition {
+    pub fn new(label: FootnoteLabel, text_tree: text::TextTree, span: SourceSpan) -> Self {
+        Self {
+            label,
+            text_tree,
+            span,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn text_tree(&self) -> &text::TextTree {
+        &self.text_tree
+    }
+
+    pub fn span(&self) -> &SourceSpan {
+        &self.span
+    }
+}
@@ -6,11 +6,21 @@ pub(crate) type ImageSource = String;
 pub(crate) struct ImageBlock {
     text_tree: text::TextTree,
     src: ImageSource,
+    title: Option<String>,
 }
 
 impl ImageBlock {
     pub fn new(text_tree: text::TextTree, src: ImageSource) -> Self {
-        Self { text_tree, src }
+        Self {
+            text_tree,
+            src,
+            title: None,
+        }
+    }
+
+    pub fn with_title(mut self, title: Option<String>) -> Self {
+        self.title = title;
+        self
     }
 
     pub fn text_tree(&self) -> &text::TextTree {
@@ -20,4 +30,8 @@ impl ImageBlock {
     pub fn src(&self) -> &str {
         &self.src
     }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
 }
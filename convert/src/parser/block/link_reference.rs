@@ -0,0 +1,44 @@
+/// Block of one or more link reference definitions like `[label]: https://example.com`.
+#[derive(Debug)]
+pub(crate) struct LinkReferenceBlock {
+    definitions: Vec<LinkReferenceDefinition>,
+}
+
+#[derive(Debug)]
+pub(crate) struct LinkReferenceDefinition {
+    label: String,
+    target: String,
+    title: Option<String>,
+}
+
+impl LinkReferenceBlock {
+    pub fn new(definitions: Vec<LinkReferenceDefinition>) -> Self {
+        Self { definitions }
+    }
+
+    pub fn definitions(&self) -> &[LinkReferenceDefinition] {
+        &self.definitions
+    }
+}
+
+impl LinkReferenceDefinition {
+    pub fn new(label: String, target: String, title: Option<String>) -> Self {
+        Self {
+            label,
+            target,
+            title,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+}
@@ -1,4 +1,4 @@
-pub(crate) use self::tree::{ListNodeKind, ListNodeStyle, ListTree, ListNodeId};
+pub(crate) use self::tree::{ListNodeId, ListNodeKind, ListNodeStyle, ListTree};
 
 mod tree;
 
@@ -12,6 +12,10 @@ impl ListBlock {
         Self { tree }
     }
 
+    pub fn tree(&self) -> &ListTree {
+        &self.tree
+    }
+
     pub fn into_tree(self) -> ListTree {
         self.tree
     }
@@ -7,7 +7,13 @@ pub(crate) type ListNodeId = usize;
 #[derive(Debug)]
 pub(crate) enum ListNodeKind {
     Parent,
-    Leaf { text_tree: text::TextTree },
+    Leaf {
+        text_tree: text::TextTree,
+        /// Symbol the item starts with in the source, like `-` or `1.`.
+        symbol: String,
+        /// Whether the task of a task list item like `- [x] Done` is done, `None` for regular items.
+        checked: Option<bool>,
+    },
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -59,7 +65,9 @@ impl ListNodeKind {
     pub(crate) fn to_string(&self, level: usize) -> String {
         match self {
             ListNodeKind::Parent => "[Parent]".to_string(),
-            ListNodeKind::Leaf { text_tree } => {
+            ListNodeKind::Leaf {
+                text_tree, checked, ..
+            } => {
                 let text_tree_str = text_tree.to_string();
                 let text_tree_lines: Vec<&str> = text_tree_str.lines().collect();
                 let mut text_tree_representation = String::new();
@@ -68,7 +76,13 @@ impl ListNodeKind {
                     text_tree_representation.push_str(&format!("{}{}\n", indent, line));
                 }
 
-                format!("[Item]\n{}", text_tree_representation.trim_end()).to_string()
+                let name = match checked {
+                    Some(true) => "[Item](checked)",
+                    Some(false) => "[Item](unchecked)",
+                    None => "[Item]",
+                };
+
+                format!("{}\n{}", name, text_tree_representation.trim_end()).to_string()
             }
         }
     }
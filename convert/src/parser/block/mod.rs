@@ -1,14 +1,18 @@
 use crate::util::SourceSpan;
+use crate::warning::Warning;
 
 pub(crate) use self::{
-    code::CodeBlock, function::FunctionBlock, heading::HeadingBlock, image::ImageBlock,
-    list::ListBlock, quote::QuoteBlock, table::TableBlock, text::TextBlock,
+    code::CodeBlock, footnote::FootnoteBlock, function::FunctionBlock, heading::HeadingBlock,
+    image::ImageBlock, link_reference::LinkReferenceBlock, list::ListBlock, quote::QuoteBlock,
+    table::TableBlock, text::TextBlock,
 };
 
 pub(crate) mod code;
+pub(crate) mod footnote;
 pub(crate) mod function;
 pub(crate) mod heading;
 pub(crate) mod image;
+pub(crate) mod link_reference;
 pub(crate) mod list;
 pub(crate) mod quote;
 pub(crate) mod table;
@@ -18,6 +22,7 @@ pub(crate) mod text;
 pub(crate) struct ParsedBlock {
     kind: ParsedBlockKind,
     span: SourceSpan,
+    warnings: Vec<Warning>,
 }
 
 #[derive(Debug)]
@@ -31,11 +36,27 @@ pub(crate) enum ParsedBlockKind {
     Code(CodeBlock),
     Function(FunctionBlock),
     HorizontalRule,
+    Footnote(FootnoteBlock),
+    LinkReference(LinkReferenceBlock),
 }
 
 impl ParsedBlock {
     pub(crate) fn new(kind: ParsedBlockKind, span: SourceSpan) -> Self {
-        Self { kind, span }
+        Self {
+            kind,
+            span,
+            warnings: Vec::new(),
+        }
+    }
+
+    pub(crate) fn with_warning(mut self, warning: Warning) -> Self {
+        self.warnings.push(warning);
+        self
+    }
+
+    /// Take the warnings found while parsing the block.
+    pub(crate) fn take_warnings(&mut self) -> Vec<Warning> {
+        std::mem::take(&mut self.warnings)
     }
 
     pub(crate) fn kind(&self) -> &ParsedBlockKind {
@@ -50,39 +71,56 @@ impl ParsedBlock {
         &self.span
     }
 
+    #[cfg(test)]
     pub(crate) fn is_text(&self) -> bool {
         matches!(self.kind, ParsedBlockKind::Text(_))
     }
 
+    #[cfg(test)]
     pub(crate) fn is_list(&self) -> bool {
         matches!(self.kind, ParsedBlockKind::List(_))
     }
 
+    #[cfg(test)]
     pub(crate) fn is_heading(&self) -> bool {
         matches!(self.kind, ParsedBlockKind::Heading(_))
     }
 
+    #[cfg(test)]
     pub(crate) fn is_table(&self) -> bool {
         matches!(self.kind, ParsedBlockKind::Table(_))
     }
 
+    #[cfg(test)]
     pub(crate) fn is_image(&self) -> bool {
         matches!(self.kind, ParsedBlockKind::Image(_))
     }
 
+    #[cfg(test)]
     pub(crate) fn is_quote(&self) -> bool {
         matches!(self.kind, ParsedBlockKind::Quote(_))
     }
 
+    #[cfg(test)]
     pub(crate) fn is_code(&self) -> bool {
         matches!(self.kind, ParsedBlockKind::Code(_))
     }
 
+    #[cfg(test)]
     pub(crate) fn is_function(&self) -> bool {
         matches!(self.kind, ParsedBlockKind::Function(_))
     }
 
+    #[cfg(test)]
     pub(crate) fn is_horizontal_rule(&self) -> bool {
         matches!(self.kind, ParsedBlockKind::HorizontalRule)
     }
+
+    pub(crate) fn is_footnote(&self) -> bool {
+        matches!(self.kind, ParsedBlockKind::Footnote(_))
+    }
+
+    pub(crate) fn is_link_reference(&self) -> bool {
+        matches!(self.kind, ParsedBlockKind::LinkReference(_))
+    }
 }
@@ -12,6 +12,10 @@ impl QuoteBlock {
         Self { tree }
     }
 
+    pub fn tree(&self) -> &QuoteTree {
+        &self.tree
+    }
+
     pub fn into_tree(self) -> QuoteTree {
         self.tree
     }
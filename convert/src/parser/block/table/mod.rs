@@ -1,4 +1,5 @@
 pub(crate) use self::cell::TableCell;
+use crate::util::Alignment;
 
 mod cell;
 
@@ -8,11 +9,22 @@ pub(crate) type TableRow = Vec<TableCell>;
 pub(crate) struct TableBlock {
     header_row: TableRow,
     rows: Vec<TableRow>,
+    alignments: Vec<Option<Alignment>>,
 }
 
 impl TableBlock {
     pub fn new(header_row: TableRow, rows: Vec<TableRow>) -> Self {
-        Self { header_row, rows }
+        Self {
+            header_row,
+            rows,
+            alignments: Vec::new(),
+        }
+    }
+
+    /// Set the alignment of each column as given by the delimiter row.
+    pub fn with_alignments(mut self, alignments: Vec<Option<Alignment>>) -> Self {
+        self.alignments = alignments;
+        self
     }
 
     pub fn header_row(&self) -> &TableRow {
@@ -23,7 +35,17 @@ impl TableBlock {
         self.rows.get(index)
     }
 
+    /// Number of columns, which is given by the header row.
+    pub fn column_count(&self) -> usize {
+        self.header_row.len()
+    }
+
     pub fn row_count(&self) -> usize {
         self.rows.len()
     }
+
+    /// Alignment of the column with the given index, if any.
+    pub fn alignment(&self, column_index: usize) -> Option<Alignment> {
+        self.alignments.get(column_index).copied().flatten()
+    }
 }
@@ -1,5 +1,6 @@
 use std::fmt::{Display, Formatter};
 
+use crate::parser::block::footnote::FootnoteLabel;
 use crate::parser::block::function::{FunctionName, FunctionParameters};
 use crate::util::SourceSpan;
 
@@ -13,17 +14,32 @@ pub(crate) enum TextNodeKind {
     },
     Bold,
     Italic,
+    Strikethrough,
     Code,
     Link {
         target: String,
+        title: Option<String>,
     },
     Image {
         src: String,
+        title: Option<String>,
+    },
+    LinkReference {
+        reference: String,
+        src: String,
+    },
+    ImageReference {
+        reference: String,
+        src: String,
     },
     Function {
         name: FunctionName,
         parameters: FunctionParameters,
     },
+    FootnoteReference {
+        label: FootnoteLabel,
+    },
+    LineBreak,
 }
 
 #[derive(Debug)]
@@ -72,9 +88,26 @@ impl Display for TextNodeKind {
             TextNodeKind::Text { src } => write!(f, "[Text]({})", src),
             TextNodeKind::Bold => write!(f, "[Bold]"),
             TextNodeKind::Italic => write!(f, "[Italic]"),
+            TextNodeKind::Strikethrough => write!(f, "[Strikethrough]"),
             TextNodeKind::Code => write!(f, "[Code]"),
-            TextNodeKind::Link { target } => write!(f, "[Link]({})", target),
-            TextNodeKind::Image { src } => write!(f, "[Image]({})", src),
+            TextNodeKind::LineBreak => write!(f, "[LineBreak]"),
+            TextNodeKind::Link { target, title } => match title {
+                Some(title) => write!(f, "[Link]({}, {})", target, title),
+                None => write!(f, "[Link]({})", target),
+            },
+            TextNodeKind::Image { src, title } => match title {
+                Some(title) => write!(f, "[Image]({}, {})", src, title),
+                None => write!(f, "[Image]({})", src),
+            },
+            TextNodeKind::LinkReference { reference, .. } => {
+                write!(f, "[LinkReference]({})", reference)
+            }
+            TextNodeKind::ImageReference { reference, .. } => {
+                write!(f, "[ImageReference]({})", reference)
+            }
+            TextNodeKind::FootnoteReference { label } => {
+                write!(f, "[FootnoteReference]({})", label)
+            }
             TextNodeKind::Function { name, parameters } => {
                 let mut param_strings = parameters
                     .iter()
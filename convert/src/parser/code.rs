@@ -1,95 +1,241 @@
+use std::collections::HashMap;
+use std::fmt;
+
 use crate::parser::block::code::{CodeBlock, LanguageIdentifier};
 use crate::parser::block::ParsedBlockKind;
 use crate::parser::{ParseError, ParseResult, ParsedBlock};
-use crate::util::SourceSpan;
+use crate::util::{
+    indentation_width, strip_code_indentation, strip_leading_spaces, SourceSpan, CODE_INDENT_WIDTH,
+};
 
 pub(crate) struct CodeParser {
     src: String,
     span: SourceSpan,
 }
 
+/// Fence a code block is started and ended with, like "```" or "~~~~".
+struct Fence {
+    c: char,
+    length: usize,
+}
+
 struct CodeBlockHeader {
     offset: usize,
     language_identifier: Option<LanguageIdentifier>,
+    attributes: HashMap<String, String>,
+    fence: Fence,
+
+    /// Whether the whole code block is written on a single line like "```code```".
+    is_single_line: bool,
 }
 
 struct CodeBlockFooter {
     offset: usize,
 }
 
+impl fmt::Display for Fence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.c.to_string().repeat(self.length))
+    }
+}
+
 impl CodeParser {
     pub fn new(src: String, span: SourceSpan) -> Self {
         Self { src, span }
     }
 
     pub fn parse(self) -> ParseResult<ParsedBlock> {
-        let header = self.find_header()?;
-        let footer = self.find_footer()?;
-
-        let code_src = self.src[header.offset..footer.offset].trim().to_string();
+        let code_block = if indentation_width(&self.src) >= CODE_INDENT_WIDTH {
+            self.parse_indented()
+        } else {
+            self.parse_fenced()?
+        };
 
         Ok(ParsedBlock::new(
-            ParsedBlockKind::Code(CodeBlock::new(header.language_identifier, code_src)),
+            ParsedBlockKind::Code(code_block),
             self.span,
         ))
     }
 
-    fn find_header(&self) -> ParseResult<CodeBlockHeader> {
-        let mut offset = 0;
+    fn parse_fenced(&self) -> ParseResult<CodeBlock> {
+        let header = self.find_header()?;
+        let footer = self.find_footer(&header.fence, header.offset)?;
 
-        let trimmed_src = self.src.trim_start();
-        offset += self.src.len() - trimmed_src.len();
+        let code_src = &self.src[header.offset..footer.offset];
+        let code_src = if header.is_single_line {
+            code_src.trim().to_string()
+        } else {
+            // The indentation of the opening fence is stripped from all lines, any further indentation is kept
+            let fence_indentation = indentation_width(&self.src);
+            code_src
+                .trim_start_matches('\n')
+                .trim_end()
+                .lines()
+                .map(|line| strip_leading_spaces(line, fence_indentation))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        Ok(CodeBlock::new(header.language_identifier, code_src).with_attributes(header.attributes))
+    }
 
-        if trimmed_src.starts_with("```") {
-            offset += 3;
+    fn parse_indented(&self) -> CodeBlock {
+        let code_src = self
+            .src
+            .lines()
+            .map(strip_code_indentation)
+            .collect::<Vec<_>>()
+            .join("\n");
 
-            let mut language_identifier = String::new();
-            for c in trimmed_src[3..].chars() {
-                match c {
-                    ' ' | '\t' | '\n' => break,
-                    '`' => {
-                        language_identifier.clear();
-                        break;
+        CodeBlock::indented(code_src.trim_end().to_string())
+    }
+
+    fn find_header(&self) -> ParseResult<CodeBlockHeader> {
+        let trimmed_src = self.src.trim_start();
+        let offset = self.src.len() - trimmed_src.len();
+
+        let fence_char = trimmed_src
+            .chars()
+            .next()
+            .filter(|c| *c == '`' || *c == '~');
+        if let Some(c) = fence_char {
+            let length = trimmed_src.chars().take_while(|next| *next == c).count();
+
+            if length >= 3 {
+                let fence = Fence { c, length };
+                let rest = &trimmed_src[length..];
+
+                return Ok(match rest.find('\n') {
+                    Some(line_end) => {
+                        // The info string is the rest of the line, starting with the language
+                        let info_string = rest[..line_end].trim();
+                        let (language, attributes_src) = info_string
+                            .split_once(char::is_whitespace)
+                            .unwrap_or((info_string, ""));
+                        let (language_identifier, attributes_src) =
+                            if language.is_empty() || language.contains(['`', '=', '"']) {
+                                (None, info_string)
+                            } else {
+                                (Some(language.to_string()), attributes_src)
+                            };
+
+                        CodeBlockHeader {
+                            offset: offset + length + line_end + 1,
+                            language_identifier,
+                            attributes: parse_info_string_attributes(attributes_src),
+                            fence,
+                            is_single_line: false,
+                        }
+                    }
+                    None => {
+                        let mut language_identifier = String::new();
+                        for c in rest.chars() {
+                            match c {
+                                ' ' | '\t' => break,
+                                '`' | '~' => {
+                                    language_identifier.clear();
+                                    break;
+                                }
+                                _ => language_identifier.push(c),
+                            };
+                        }
+
+                        CodeBlockHeader {
+                            offset: offset + length + language_identifier.len(),
+                            language_identifier: if language_identifier.is_empty() {
+                                None
+                            } else {
+                                Some(language_identifier)
+                            },
+                            attributes: HashMap::new(),
+                            fence,
+                            is_single_line: true,
+                        }
                     }
-                    _ => language_identifier.push(c),
-                };
+                });
             }
-
-            return Ok(CodeBlockHeader {
-                offset: offset + language_identifier.len(),
-                language_identifier: if language_identifier.is_empty() {
-                    None
-                } else {
-                    Some(language_identifier)
-                },
-            });
         }
 
         Err(ParseError {
             message: "Code block must be started with '```'".to_string(),
             source_position: self.span.start.clone(),
+            span: None,
+            help: Some("Start the code block with a line containing '```' or '~~~'".to_string()),
         })
     }
 
-    fn find_footer(&self) -> ParseResult<CodeBlockFooter> {
+    /// Find the closing fence, which must be at least as long as the opening fence.
+    fn find_footer(&self, fence: &Fence, header_offset: usize) -> ParseResult<CodeBlockFooter> {
         let trimmed_src = self.src.trim_end();
-        let trimmed_src_len = trimmed_src.len();
-
-        let mut offset = trimmed_src_len;
+        let closing_fence_length = trimmed_src
+            .chars()
+            .rev()
+            .take_while(|c| *c == fence.c)
+            .count();
 
-        if self.src.ends_with("```") {
-            offset -= 3;
+        if closing_fence_length >= fence.length {
+            let offset = (trimmed_src.len() - closing_fence_length).max(header_offset);
 
             return Ok(CodeBlockFooter { offset });
         }
 
         Err(ParseError {
-            message: "Code block must be ended with '```'".to_string(),
+            message: format!("Code block must be ended with '{}'", fence),
             source_position: self.span.end.clone(),
+            span: None,
+            help: Some(format!(
+                "Close the code block with a line containing '{}'",
+                fence
+            )),
         })
     }
 }
 
+/// Parse the attributes of an info string like `title="main.rs" linenos highlight=2-4`.
+/// Attributes without a value are flags set to "true".
+fn parse_info_string_attributes(src: &str) -> HashMap<String, String> {
+    let mut attributes = HashMap::new();
+    let mut chars = src.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        let mut key = String::new();
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '=') {
+            key.push(c);
+        }
+        if key.is_empty() {
+            // Skip stray chars like a '=' without key
+            if chars.next().is_none() {
+                break;
+            }
+            continue;
+        }
+
+        let mut value = String::new();
+        if chars.next_if_eq(&'=').is_some() {
+            if chars.next_if_eq(&'"').is_some() {
+                for c in chars.by_ref() {
+                    if c == '"' {
+                        break;
+                    }
+                    value.push(c);
+                }
+            } else {
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                    value.push(c);
+                }
+            }
+        } else {
+            value.push_str("true");
+        }
+
+        attributes.insert(key, value);
+    }
+
+    attributes
+}
+
 #[cfg(test)]
 mod tests {
     use crate::util::SourcePosition;
@@ -209,12 +355,11 @@ I want it formatted in **bold** and as inline `code`.```";
 
         assert_eq!(*code_block.language(), Some("md".to_string()));
 
+        // The rest of the first line is the info string rather than code
         let code_src = code_block.src();
         assert_eq!(
             code_src,
-            "# Title
-
-This is a simple paragraph.
+            "This is a simple paragraph.
 
 I want it formatted in **bold** and as inline `code`."
         );
@@ -341,4 +486,133 @@ I want it formatted in **bold** and as inline `code`.
         let code_src = code_block.src();
         assert_eq!(code_src, "a");
     }
+
+    #[test]
+    fn parse_code_block_with_trailing_newline() {
+        let src = "```js\nconsole.log('Hello World');\n```\n";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(3, 4));
+        let parser = CodeParser::new(src.to_string(), span);
+
+        let code_block = match parser.parse().unwrap().into_kind() {
+            ParsedBlockKind::Code(code_block) => code_block,
+            _ => panic!("Expected code block"),
+        };
+
+        assert_eq!(code_block.src(), "console.log('Hello World');");
+    }
+
+    #[test]
+    fn parse_tilde_fenced_code_block_with_info_string() {
+        let src = "~~~rust title=main.rs\nfn main() {\n    println!(\"```\");\n}\n~~~";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(5, 4));
+        let parser = CodeParser::new(src.to_string(), span);
+
+        let code_block = match parser.parse().unwrap().into_kind() {
+            ParsedBlockKind::Code(code_block) => code_block,
+            _ => panic!("Expected code block"),
+        };
+
+        assert_eq!(*code_block.language(), Some("rust".to_string()));
+        assert_eq!(code_block.src(), "fn main() {\n    println!(\"```\");\n}");
+    }
+
+    #[test]
+    fn parse_long_fence_containing_backticks() {
+        let src = "````md\n```js\ncode\n```\n````";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(5, 5));
+        let parser = CodeParser::new(src.to_string(), span);
+
+        let code_block = match parser.parse().unwrap().into_kind() {
+            ParsedBlockKind::Code(code_block) => code_block,
+            _ => panic!("Expected code block"),
+        };
+
+        assert_eq!(*code_block.language(), Some("md".to_string()));
+        assert_eq!(code_block.src(), "```js\ncode\n```");
+    }
+
+    #[test]
+    fn fail_on_too_short_footer() {
+        let src = "````\ncode\n```";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(3, 4));
+        let parser = CodeParser::new(src.to_string(), span);
+
+        let result = parser.parse();
+        assert_eq!(
+            result.unwrap_err().message,
+            "Code block must be ended with '````'"
+        );
+    }
+
+    #[test]
+    fn parse_indented_code_block() {
+        let src = "    fn main() {\n\n\t    println!();\n    }";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(4, 6));
+        let parser = CodeParser::new(src.to_string(), span);
+
+        let code_block = match parser.parse().unwrap().into_kind() {
+            ParsedBlockKind::Code(code_block) => code_block,
+            _ => panic!("Expected code block"),
+        };
+
+        assert_eq!(*code_block.language(), None);
+        assert!(!code_block.is_fenced());
+        assert_eq!(code_block.src(), "fn main() {\n\n    println!();\n}");
+    }
+
+    #[test]
+    fn parse_indented_fence_and_strip_its_indentation() {
+        let src = "  ```rust\n  fn main() {\n      run();\n }\n  ```";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(5, 6));
+        let parser = CodeParser::new(src.to_string(), span);
+
+        let code_block = match parser.parse().unwrap().into_kind() {
+            ParsedBlockKind::Code(code_block) => code_block,
+            _ => panic!("Expected code block"),
+        };
+
+        assert_eq!(*code_block.language(), Some("rust".to_string()));
+        assert_eq!(code_block.src(), "fn main() {\n    run();\n}");
+    }
+
+    #[test]
+    fn parse_info_string_attributes_after_language() {
+        let src = "```rust title=\"Main file.rs\" linenos highlight=2-4\nfn main() {}\n```";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(3, 4));
+        let parser = CodeParser::new(src.to_string(), span);
+
+        let code_block = match parser.parse().unwrap().into_kind() {
+            ParsedBlockKind::Code(code_block) => code_block,
+            _ => panic!("Expected code block"),
+        };
+
+        assert_eq!(*code_block.language(), Some("rust".to_string()));
+        assert_eq!(
+            code_block.attributes(),
+            &HashMap::from([
+                ("title".to_string(), "Main file.rs".to_string()),
+                ("linenos".to_string(), "true".to_string()),
+                ("highlight".to_string(), "2-4".to_string()),
+            ])
+        );
+        assert_eq!(code_block.src(), "fn main() {}");
+    }
+
+    #[test]
+    fn parse_info_string_attributes_without_language() {
+        let src = "``` title=notes.txt\nSome notes\n```";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(3, 4));
+        let parser = CodeParser::new(src.to_string(), span);
+
+        let code_block = match parser.parse().unwrap().into_kind() {
+            ParsedBlockKind::Code(code_block) => code_block,
+            _ => panic!("Expected code block"),
+        };
+
+        assert_eq!(*code_block.language(), None);
+        assert_eq!(
+            code_block.attributes(),
+            &HashMap::from([("title".to_string(), "notes.txt".to_string())])
+        );
+    }
 }
@@ -0,0 +1,141 @@
+use crate::categorizer::is_footnote_label;
+use crate::parser::block::footnote::{FootnoteBlock, FootnoteDefinition};
+use crate::parser::block::ParsedBlockKind;
+use crate::parser::text::TextParser;
+use crate::parser::{ParseError, ParseResult, ParsedBlock};
+use crate::util::{SourcePosition, SourceSpan};
+
+pub(crate) struct FootnoteParser {
+    src: String,
+    span: SourceSpan,
+}
+
+struct DefinitionInSource {
+    label: String,
+    content: String,
+    span: SourceSpan,
+}
+
+impl FootnoteParser {
+    pub fn new(src: String, span: SourceSpan) -> Self {
+        Self { src, span }
+    }
+
+    pub fn parse(self) -> ParseResult<ParsedBlock> {
+        let mut definitions = Vec::new();
+
+        for definition in self.find_definitions_in_src()? {
+            let text_parser = TextParser::new(definition.content, definition.span.clone());
+            let text_block = text_parser.parse()?;
+            let text_tree = if let ParsedBlockKind::Text(text_block) = text_block.into_kind() {
+                text_block.into_tree()
+            } else {
+                unreachable!()
+            };
+
+            definitions.push(FootnoteDefinition::new(
+                definition.label,
+                text_tree,
+                definition.span,
+            ));
+        }
+
+        Ok(ParsedBlock::new(
+            ParsedBlockKind::Footnote(FootnoteBlock::new(definitions)),
+            self.span,
+        ))
+    }
+
+    fn find_definitions_in_src(&self) -> ParseResult<Vec<DefinitionInSource>> {
+        let mut definitions: Vec<DefinitionInSource> = Vec::new();
+
+        for (index, line) in self.src.lines().enumerate() {
+            let line_number = self.span.start.line + index;
+            let line_end = SourcePosition::new(line_number, line.chars().count() + 1);
+
+            if let Some((label, content)) = split_definition_start(line) {
+                let column = line.chars().count() - content.chars().count() + 1;
+
+                definitions.push(DefinitionInSource {
+                    label: label.to_string(),
+                    content: content.to_string(),
+                    span: SourceSpan::new(SourcePosition::new(line_number, column), line_end),
+                });
+            } else if let Some(definition) = definitions.last_mut() {
+                // Continuation of the previous definition
+                definition.content.push('\n');
+                definition.content.push_str(line.trim());
+                definition.span.end = line_end;
+            } else {
+                return Err(ParseError {
+                    message: "Expected footnote definition".to_string(),
+                    source_position: self.span.start.clone(),
+                    span: None,
+                    help: Some("Start footnote definitions with '[^label]:'".to_string()),
+                });
+            }
+        }
+
+        Ok(definitions)
+    }
+}
+
+/// Split a line starting a footnote definition like `[^1]: Text` into label and content.
+fn split_definition_start(line: &str) -> Option<(&str, &str)> {
+    let (label, content) = line.strip_prefix("[^")?.split_once("]:")?;
+
+    if is_footnote_label(label) {
+        Some((label, content.trim_start()))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_footnote_definitions() {
+        let src = "\
+[^1]: The first footnote
+  continued on the next line.
+[^note]: A *formatted* footnote.";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(3, 33));
+        let parser = FootnoteParser::new(src.to_string(), span);
+
+        let parsed_block = parser.parse().unwrap();
+
+        let footnote_block = if let ParsedBlockKind::Footnote(block) = parsed_block.into_kind() {
+            block
+        } else {
+            panic!("Expected footnote block");
+        };
+
+        let definitions = footnote_block.definitions();
+        assert_eq!(definitions.len(), 2);
+
+        assert_eq!(definitions[0].label(), "1");
+        assert_eq!(
+            definitions[0].span(),
+            &SourceSpan::new(SourcePosition::new(1, 7), SourcePosition::new(2, 30))
+        );
+        assert_eq!(
+            format!("{}", definitions[0].text_tree()),
+            "- [Root]
+  - [Text](The first footnote continued on the next line.)
+"
+        );
+
+        assert_eq!(definitions[1].label(), "note");
+        assert_eq!(
+            format!("{}", definitions[1].text_tree()),
+            "- [Root]
+  - [Text](A )
+  - [Italic]
+    - [Text](formatted)
+  - [Text]( footnote.)
+"
+        );
+    }
+}
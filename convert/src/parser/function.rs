@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
-use crate::parser::{ParsedBlock, ParseError, ParseResult};
 use crate::parser::block::function::FunctionBlock;
 use crate::parser::block::ParsedBlockKind;
+use crate::parser::{ParseError, ParseResult, ParsedBlock};
 use crate::util::SourceSpan;
 
 pub(crate) struct FunctionParser {
@@ -26,6 +26,8 @@ impl FunctionParser {
                     return Err(ParseError {
                         message: "Unexpected whitespace in function name".to_owned(),
                         source_position: self.span.start.clone(),
+                        span: None,
+                        help: Some("Function names must not contain whitespace".to_owned()),
                     });
                 }
                 '(' => {
@@ -43,16 +45,20 @@ impl FunctionParser {
             return Err(ParseError {
                 message: "Function name is empty".to_owned(),
                 source_position: self.span.start.clone(),
+                span: None,
+                help: Some("Add a name after '#', for example `#break`".to_owned()),
             });
         }
 
         let mut parameters = HashMap::new();
         let src = &src[offset..];
         if src.chars().nth(0) == Some('(') {
-            if src.chars().last() != Some(')') {
+            if !src.ends_with(')') {
                 return Err(ParseError {
                     message: "Expected closing parenthesis for function parameters".to_owned(),
                     source_position: self.span.start.clone(),
+                    span: None,
+                    help: Some("Add ')' at the end of the function parameters".to_owned()),
                 });
             }
 
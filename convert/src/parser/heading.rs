@@ -1,8 +1,9 @@
+use crate::categorizer::setext_heading_level;
 use crate::parser::block::heading::HeadingBlock;
 use crate::parser::block::ParsedBlockKind;
 use crate::parser::text::TextParser;
 use crate::parser::{ParseResult, ParsedBlock};
-use crate::util::SourceSpan;
+use crate::util::{SourcePosition, SourceSpan};
 
 pub(crate) struct HeadingParser {
     src: String,
@@ -12,6 +13,7 @@ pub(crate) struct HeadingParser {
 struct FindHeadingLevelResult {
     heading_level: usize,
     offset: usize,
+    end: usize,
 }
 
 impl HeadingParser {
@@ -23,10 +25,16 @@ impl HeadingParser {
         let FindHeadingLevelResult {
             heading_level,
             offset,
+            end,
         } = self.find_heading_level();
 
-        let rest_str = self.src[offset..].to_owned();
-        let text_parser = TextParser::new(rest_str, self.span.clone());
+        // The text starts after the `#` prefix of ATX headings, which is on the first line
+        let rest_str = self.src[offset..end].to_owned();
+        let text_span = SourceSpan::new(
+            SourcePosition::new(self.span.start.line, self.span.start.column + offset),
+            self.span.end.clone(),
+        );
+        let text_parser = TextParser::new(rest_str, text_span);
         let parsed_block = text_parser.parse()?;
         let text_block = if let ParsedBlockKind::Text(parsed_block) = parsed_block.into_kind() {
             parsed_block
@@ -52,17 +60,49 @@ impl HeadingParser {
             }
         }
 
+        let is_atx_heading = heading_level > 0 && self.src[heading_level..].starts_with(' ');
+        if !is_atx_heading {
+            if let Some(result) = self.find_setext_heading_level() {
+                return result;
+            }
+        }
+
+        let offset = heading_level + 1;
         FindHeadingLevelResult {
             heading_level,
-            offset: heading_level + 1,
+            offset,
+            end: self
+                .find_closing_sequence()
+                .unwrap_or(self.src.len())
+                .max(offset),
         }
     }
+
+    /// Find the start of the optional closing sequence of an ATX heading like `# Title #`.
+    /// An escaped `\#` at the end of the heading is not a closing sequence.
+    fn find_closing_sequence(&self) -> Option<usize> {
+        let content = self.src.trim_end();
+        let without_closing_sequence = content.trim_end_matches('#');
+
+        let has_closing_sequence = without_closing_sequence.len() < content.len()
+            && without_closing_sequence.ends_with([' ', '\t']);
+        has_closing_sequence.then(|| without_closing_sequence.trim_end().len())
+    }
+
+    /// Find the level of a heading underlined by `===` (level 1) or `---` (level 2).
+    fn find_setext_heading_level(&self) -> Option<FindHeadingLevelResult> {
+        let (content, underline) = self.src.trim_end().rsplit_once('\n')?;
+
+        setext_heading_level(underline).map(|heading_level| FindHeadingLevelResult {
+            heading_level,
+            offset: 0,
+            end: content.len(),
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::util::SourcePosition;
-
     use super::*;
 
     #[test]
@@ -167,6 +207,84 @@ with `multiple` *lines*!!!";
   - [Italic]
     - [Text](lines)
   - [Text](!!!)
+"
+        );
+    }
+
+    #[test]
+    fn should_strip_closing_sequence_unless_escaped() {
+        for (src, text) in [
+            ("## Title ##", "Title"),
+            ("## Title #####   ", "Title"),
+            ("## Title#", "Title#"),
+            ("## Title \\#", "Title #"),
+            ("## C\\# \\[draft\\]", "C# [draft]"),
+        ] {
+            let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 12));
+
+            let parser = HeadingParser::new(src.to_string(), span);
+            let heading_block =
+                if let ParsedBlockKind::Heading(b) = parser.parse().unwrap().into_kind() {
+                    b
+                } else {
+                    panic!("Expected heading block");
+                };
+
+            assert_eq!(heading_block.level(), 2);
+            assert_eq!(
+                format!("{}", heading_block.text_tree()),
+                format!("- [Root]\n  - [Text]({})\n", text)
+            );
+        }
+    }
+
+    #[test]
+    fn should_parse_setext_headings() {
+        for (src, level) in [
+            ("This is a heading\n=================", 1),
+            ("This is a heading\n---", 2),
+        ] {
+            let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(2, 4));
+
+            let parser = HeadingParser::new(src.to_string(), span);
+            let heading_block =
+                if let ParsedBlockKind::Heading(b) = parser.parse().unwrap().into_kind() {
+                    b
+                } else {
+                    panic!("Expected heading block");
+                };
+
+            assert_eq!(heading_block.level(), level);
+            assert_eq!(
+                format!("{}", heading_block.text_tree()),
+                "- [Root]
+  - [Text](This is a heading)
+"
+            );
+        }
+    }
+
+    #[test]
+    fn should_parse_multi_line_setext_heading() {
+        let src = "A **multi-line**\nheading\n===";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(3, 4));
+
+        let parser = HeadingParser::new(src.to_string(), span);
+        let heading_block = if let ParsedBlockKind::Heading(b) = parser.parse().unwrap().into_kind()
+        {
+            b
+        } else {
+            panic!("Expected heading block");
+        };
+
+        assert_eq!(heading_block.level(), 1);
+        assert_eq!(
+            format!("{}", heading_block.text_tree()),
+            "- [Root]
+  - [Text](A )
+  - [Bold]
+    - [Text](multi-line)
+  - [Text]( heading)
 "
         );
     }
@@ -2,7 +2,8 @@ use crate::parser::block::image::ImageBlock;
 use crate::parser::block::ParsedBlockKind;
 use crate::parser::text::TextParser;
 use crate::parser::{ParseResult, ParsedBlock};
-use crate::util::{SourcePosition, SourceSpan};
+use crate::util::{split_link_destination, SourcePosition, SourceSpan};
+use crate::warning::{Warning, WarningCode};
 
 pub(crate) struct ImageParser {
     src: String,
@@ -63,10 +64,24 @@ impl ImageParser {
             }
         }
 
-        Ok(ParsedBlock::new(
-            ParsedBlockKind::Image(ImageBlock::new(text_tree, image_src)),
-            self.span,
-        ))
+        let (image_src, title) = match split_link_destination(&image_src) {
+            Some((image_src, title)) => (image_src.to_string(), title.map(|t| t.to_string())),
+            None => (image_src, None),
+        };
+
+        let block = ParsedBlock::new(
+            ParsedBlockKind::Image(ImageBlock::new(text_tree, image_src).with_title(title)),
+            self.span.clone(),
+        );
+        if text_src.is_empty() {
+            Ok(block.with_warning(Warning::new(
+                WarningCode::EmptyImageAlt,
+                "Image has no alternative text",
+                self.span,
+            )))
+        } else {
+            Ok(block)
+        }
     }
 }
 
@@ -134,4 +149,29 @@ mod tests {
 "
         );
     }
+
+    #[test]
+    fn should_warn_about_empty_alt_text() {
+        let src = "![](image.jpg)";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 15));
+        let parser = ImageParser::new(src.to_string(), span);
+
+        let mut parsed_block = parser.parse().unwrap();
+        assert!(parsed_block.is_image());
+
+        let warnings = parsed_block.take_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code(), WarningCode::EmptyImageAlt);
+    }
+
+    #[test]
+    fn should_not_warn_about_alt_text() {
+        let src = "![Label](image.jpg)";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 20));
+        let parser = ImageParser::new(src.to_string(), span);
+
+        let mut parsed_block = parser.parse().unwrap();
+
+        assert!(parsed_block.take_warnings().is_empty());
+    }
 }
@@ -0,0 +1,103 @@
+use crate::categorizer::split_link_reference_definition;
+use crate::parser::block::link_reference::{LinkReferenceBlock, LinkReferenceDefinition};
+use crate::parser::block::ParsedBlockKind;
+use crate::parser::{ParseError, ParseResult, ParsedBlock};
+use crate::util::{split_link_destination, SourcePosition, SourceSpan};
+
+pub(crate) struct LinkReferenceParser {
+    src: String,
+    span: SourceSpan,
+}
+
+impl LinkReferenceParser {
+    pub fn new(src: String, span: SourceSpan) -> Self {
+        Self { src, span }
+    }
+
+    pub fn parse(self) -> ParseResult<ParsedBlock> {
+        let mut definitions = Vec::new();
+
+        for (index, line) in self.src.lines().enumerate() {
+            let source_position = SourcePosition::new(self.span.start.line + index, 1);
+
+            let (label, rest) =
+                split_link_reference_definition(line).ok_or_else(|| ParseError {
+                    message: "Expected link reference definition".to_string(),
+                    source_position: source_position.clone(),
+                    span: None,
+                    help: Some(
+                        "Define link references like '[label]: https://example.com'".to_string(),
+                    ),
+                })?;
+            let (target, title) = split_link_destination(rest).ok_or_else(|| ParseError {
+                message: format!("Invalid target of link reference '{}'", label),
+                source_position: source_position.clone(),
+                span: None,
+                help: Some(
+                    "Follow the link target by nothing but an optional title like \"Title\""
+                        .to_string(),
+                ),
+            })?;
+
+            definitions.push(LinkReferenceDefinition::new(
+                label.to_string(),
+                target.to_string(),
+                title.map(|title| title.to_string()),
+            ));
+        }
+
+        Ok(ParsedBlock::new(
+            ParsedBlockKind::LinkReference(LinkReferenceBlock::new(definitions)),
+            self.span,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_link_reference_definitions() {
+        let src = "\
+[docs]: https://example.com/docs \"The docs\"
+[My Page]: <my page.html>
+[other]: other.html (Other)";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(3, 28));
+        let parser = LinkReferenceParser::new(src.to_string(), span);
+
+        let parsed_block = parser.parse().unwrap();
+
+        let block = if let ParsedBlockKind::LinkReference(block) = parsed_block.into_kind() {
+            block
+        } else {
+            panic!("Expected link reference block");
+        };
+
+        let definitions = block
+            .definitions()
+            .iter()
+            .map(|definition| (definition.label(), definition.target(), definition.title()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            definitions,
+            vec![
+                ("docs", "https://example.com/docs", Some("The docs")),
+                ("My Page", "my page.html", None),
+                ("other", "other.html", Some("Other")),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_fail_on_text_after_target() {
+        let src = "[docs]: https://example.com/docs and more";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 42));
+        let parser = LinkReferenceParser::new(src.to_string(), span);
+
+        assert_eq!(
+            parser.parse().unwrap_err().message,
+            "Invalid target of link reference 'docs'"
+        );
+    }
+}
@@ -19,8 +19,9 @@ enum Indent {
 #[derive(Debug)]
 struct ItemInSource {
     indent: Indent,
-    _symbol: String,
+    symbol: String,
     is_ordered: bool,
+    checked: Option<bool>,
     content: String,
     span: SourceSpan,
 }
@@ -75,7 +76,11 @@ impl ListParser {
             if item.indent == required_indent_for_same_level {
                 tree.register_node(
                     parent_node_id,
-                    ListNodeKind::Leaf { text_tree },
+                    ListNodeKind::Leaf {
+                        text_tree,
+                        symbol: item.symbol,
+                        checked: item.checked,
+                    },
                     list_node_style,
                 );
             } else if item.indent.count() > required_indent_for_same_level.count() {
@@ -86,7 +91,11 @@ impl ListParser {
 
                 tree.register_node(
                     new_parent_node_id,
-                    ListNodeKind::Leaf { text_tree },
+                    ListNodeKind::Leaf {
+                        text_tree,
+                        symbol: item.symbol,
+                        checked: item.checked,
+                    },
                     list_node_style,
                 );
             } else {
@@ -96,7 +105,11 @@ impl ListParser {
 
                 tree.register_node(
                     new_parent_node_id,
-                    ListNodeKind::Leaf { text_tree },
+                    ListNodeKind::Leaf {
+                        text_tree,
+                        symbol: item.symbol,
+                        checked: item.checked,
+                    },
                     list_node_style,
                 );
             }
@@ -124,12 +137,15 @@ impl ListParser {
             if is_start_of_new_item {
                 let indent_count = indent.count();
                 let symbol_length = symbol.len();
+                let (checked, content) =
+                    split_task_marker(&line[indent_count + symbol_length + 1..]);
 
                 items.push(ItemInSource {
                     indent,
-                    _symbol: symbol,
+                    symbol,
                     is_ordered,
-                    content: line[indent_count + symbol_length + 1..].to_owned(),
+                    checked,
+                    content: content.to_owned(),
                     span: SourceSpan::new(
                         SourcePosition::new(line_number, 1),
                         SourcePosition::new(line_number, line.len() + 1),
@@ -137,7 +153,8 @@ impl ListParser {
                 });
             } else {
                 let last_item = items.last_mut().unwrap();
-                last_item.content.push_str(line);
+                last_item.content.push('\n');
+                last_item.content.push_str(line.trim_start());
                 last_item.span.end = SourcePosition::new(line_number, line.len() + 1);
             }
         }
@@ -152,7 +169,7 @@ impl ListParser {
     ) -> ParseResult<IsStartOfNewLineResult> {
         let mut indent = Indent::Zero;
 
-        for (index, c) in line.chars().enumerate() {
+        for (index, c) in line.char_indices() {
             match c {
                 '\t' => match indent {
                     Indent::Zero => indent = Indent::Tab(1),
@@ -163,6 +180,8 @@ impl ListParser {
                         return Err(ParseError {
                                 message: "Mixed tab and space in list item indentation. Started indenting with tab and then encountered space.".to_string(),
                                 source_position: SourcePosition::new(line_number, 1),
+                                span: None,
+                                help: Some("Indent list items using either tabs or spaces".to_string()),
                             });
                     }
                 },
@@ -175,13 +194,15 @@ impl ListParser {
                         return Err(ParseError {
                                 message: "Mixed tab and space in list item indentation. Started indenting with tab and then encountered space.".to_string(),
                                 source_position: SourcePosition::new(line_number, 1),
+                                span: None,
+                                help: Some("Indent list items using either tabs or spaces".to_string()),
                             });
                     }
                 },
                 _ => {
                     let is_valid_unordered_symbol = c == '-' || c == '*' || c == '+';
                     let is_valid_ordered_symbol =
-                        c.is_digit(10) && line.chars().nth(index + 1) == Some('.');
+                        c.is_ascii_digit() && line.chars().nth(index + 1) == Some('.');
                     let symbol = if is_valid_unordered_symbol {
                         Some(c.to_string())
                     } else if is_valid_ordered_symbol {
@@ -214,6 +235,26 @@ impl ListParser {
     }
 }
 
+/// Split off the task marker `[ ]` or `[x]` from the start of the item content (if any).
+fn split_task_marker(content: &str) -> (Option<bool>, &str) {
+    let checked = if content.starts_with("[ ]") {
+        false
+    } else if content.starts_with("[x]") || content.starts_with("[X]") {
+        true
+    } else {
+        return (None, content);
+    };
+
+    let rest = &content[3..];
+    if rest.is_empty() {
+        (Some(checked), rest)
+    } else if let Some(rest) = rest.strip_prefix(' ') {
+        (Some(checked), rest)
+    } else {
+        (None, content)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::parser::block::ParsedBlockKind;
@@ -398,6 +439,67 @@ mod tests {
         - [Text](2)
   - unordered [Item]
     - [Text](Item 3)
+"
+        );
+    }
+
+    #[test]
+    fn should_parse_task_list_items() {
+        let src = "- [ ] Todo
+- [x] Done
+- [X] Upper case
+- [ ]not a task
+1. [x] Ordered";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(5, 15));
+        let parser = ListParser::new(src.to_string(), span);
+
+        let parsed_block = parser.parse().unwrap();
+
+        let list_block = if let ParsedBlockKind::List(list_block) = parsed_block.into_kind() {
+            list_block
+        } else {
+            panic!("Expected list block");
+        };
+
+        let tree = list_block.into_tree();
+        assert_eq!(
+            format!("{}", tree),
+            "- [Parent]
+  - unordered [Item](unchecked)
+    - [Text](Todo)
+  - unordered [Item](checked)
+    - [Text](Done)
+  - unordered [Item](checked)
+    - [Text](Upper case)
+  - unordered [Item]
+    - [Text]([ ]not a task)
+  - ordered [Item](checked)
+    - [Text](Ordered)
+"
+        );
+    }
+
+    #[test]
+    fn should_keep_escaped_task_marker_as_text() {
+        let src = "- \\[x] Not a task
+- \\- Not nested";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(2, 17));
+        let parser = ListParser::new(src.to_string(), span);
+
+        let list_block =
+            if let ParsedBlockKind::List(list_block) = parser.parse().unwrap().into_kind() {
+                list_block
+            } else {
+                panic!("Expected list block");
+            };
+
+        assert_eq!(
+            format!("{}", list_block.into_tree()),
+            "- [Parent]
+  - unordered [Item]
+    - [Text]([x] Not a task)
+  - unordered [Item]
+    - [Text](- Not nested)
 "
         );
     }
@@ -2,9 +2,11 @@
 
 pub(crate) use block::{
     code::CodeBlock,
+    footnote::FootnoteDefinition,
     function::FunctionBlock,
     heading::HeadingBlock,
     image::ImageBlock,
+    link_reference::LinkReferenceDefinition,
     list::{ListBlock, ListNodeId, ListNodeKind, ListNodeStyle, ListTree},
     quote::{QuoteBlock, QuoteNodeId, QuoteNodeKind, QuoteTree},
     table::{TableBlock, TableCell, TableRow},
@@ -15,19 +17,24 @@ pub(crate) use result::{ParseError, ParseResult};
 
 use crate::categorizer::{BlockKind, CategorizedBlock};
 use crate::parser::code::CodeParser;
+use crate::parser::footnote::FootnoteParser;
 use crate::parser::function::FunctionParser;
 use crate::parser::heading::HeadingParser;
 use crate::parser::image::ImageParser;
+use crate::parser::link_reference::LinkReferenceParser;
 use crate::parser::list::ListParser;
 use crate::parser::quote::QuoteParser;
 use crate::parser::table::TableParser;
 use crate::parser::text::TextParser;
+use crate::util::SourceSpan;
 
 mod block;
 mod code;
+mod footnote;
 mod function;
 mod heading;
 mod image;
+mod link_reference;
 mod list;
 mod quote;
 mod result;
@@ -44,6 +51,30 @@ impl BlockParser {
     pub(crate) fn parse(&self, categorized_block: CategorizedBlock) -> ParseResult<ParsedBlock> {
         let (kind, src, span) = categorized_block.consume();
 
+        self.parse_as(kind, src, span)
+    }
+
+    /// Parse the given block, falling back to a text paragraph if it cannot be parsed.
+    /// The error of the failed attempt is returned along with the fallback block.
+    pub(crate) fn parse_with_fallback(
+        &self,
+        categorized_block: CategorizedBlock,
+    ) -> (ParsedBlock, Option<ParseError>) {
+        let (kind, src, span) = categorized_block.consume();
+
+        match self.parse_as(kind, src.clone(), span.clone()) {
+            Ok(block) => (block, None),
+            Err(e) => {
+                let block = TextParser::new(src.clone(), span.clone())
+                    .parse()
+                    .unwrap_or_else(|_| plain_text_block(src, span));
+
+                (block, Some(e))
+            }
+        }
+    }
+
+    fn parse_as(&self, kind: BlockKind, src: String, span: SourceSpan) -> ParseResult<ParsedBlock> {
         match kind {
             BlockKind::Text => TextParser::new(src, span).parse(),
             BlockKind::Heading => HeadingParser::new(src, span).parse(),
@@ -56,10 +87,21 @@ impl BlockParser {
             BlockKind::Image => ImageParser::new(src, span).parse(),
             BlockKind::Quote => QuoteParser::new(src, span).parse(),
             BlockKind::Function => FunctionParser::new(src, span).parse(),
+            BlockKind::Footnote => FootnoteParser::new(src, span).parse(),
+            BlockKind::LinkReference => LinkReferenceParser::new(src, span).parse(),
         }
     }
 }
 
+/// Create a text block containing the given source as is.
+fn plain_text_block(src: String, span: SourceSpan) -> ParsedBlock {
+    let mut tree = TextTree::new(span.clone());
+    let root_id = tree.root().id();
+    tree.register_node(root_id, TextNodeKind::Text { src }, span.clone());
+
+    ParsedBlock::new(ParsedBlockKind::Text(TextBlock::new(tree)), span)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::util::{SourcePosition, SourceSpan};
@@ -206,4 +248,34 @@ console.log('Hello World');
         let parsed_block = result.unwrap();
         assert!(parsed_block.is_function());
     }
+
+    #[test]
+    fn should_fall_back_to_text_block() {
+        let src = "```js
+console.log('Hello World');";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(2, 28));
+        let categorized_block = CategorizedBlock::new(BlockKind::Code, src.to_string(), span);
+
+        let parser = BlockParser::new();
+        let (parsed_block, error) = parser.parse_with_fallback(categorized_block);
+
+        assert!(parsed_block.is_text());
+        assert_eq!(
+            error.unwrap().message,
+            "Code block must be ended with '```'"
+        );
+    }
+
+    #[test]
+    fn should_not_report_error_without_fallback() {
+        let src = "This is a paragraph.";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 21));
+        let categorized_block = CategorizedBlock::new(BlockKind::Text, src.to_string(), span);
+
+        let parser = BlockParser::new();
+        let (parsed_block, error) = parser.parse_with_fallback(categorized_block);
+
+        assert!(parsed_block.is_text());
+        assert!(error.is_none());
+    }
 }
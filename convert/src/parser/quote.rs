@@ -1,7 +1,7 @@
-use crate::parser::{ParsedBlock, ParseError, ParseResult};
-use crate::parser::block::ParsedBlockKind;
 use crate::parser::block::quote::{QuoteBlock, QuoteNodeId, QuoteNodeKind, QuoteTree};
+use crate::parser::block::ParsedBlockKind;
 use crate::parser::text::TextParser;
+use crate::parser::{ParseError, ParseResult, ParsedBlock};
 use crate::util::{SourcePosition, SourceSpan};
 
 pub(crate) struct QuoteParser {
@@ -29,11 +29,10 @@ impl QuoteParser {
         let mut parent_node_ids = vec![tree.root().id()];
         let mut indents = Vec::new();
         let mut text_buffer = String::new();
-        let mut counter = 0;
         let mut start_line_number = 0;
         let mut start_offset = 0;
         let total = indented_quote_lines.len();
-        for indented_quote_line in indented_quote_lines {
+        for (counter, indented_quote_line) in indented_quote_lines.into_iter().enumerate() {
             if indents.is_empty() {
                 indents.push(indented_quote_line.indent);
                 start_line_number = indented_quote_line.line_number;
@@ -46,7 +45,7 @@ impl QuoteParser {
             let is_same_indent = current_indent == indented_quote_line.indent;
             if is_same_indent {
                 if !text_buffer.is_empty() {
-                    text_buffer.push(' ');
+                    text_buffer.push('\n');
                 }
                 text_buffer.push_str(&indented_quote_line.line);
             } else if current_indent < indented_quote_line.indent {
@@ -98,8 +97,6 @@ impl QuoteParser {
                     start_offset,
                 )?;
             }
-
-            counter += 1;
         }
 
         Ok(ParsedBlock::new(
@@ -144,8 +141,7 @@ impl QuoteParser {
     fn find_indented_quote_lines(&self) -> ParseResult<Vec<IndentedQuoteLine>> {
         let mut result = Vec::new();
 
-        let mut line_number = self.span.start.line;
-        for line in self.src.lines() {
+        for (line_number, line) in (self.span.start.line..).zip(self.src.lines()) {
             let mut indent = 0;
             let mut offset = 0;
             for c in line.chars() {
@@ -162,6 +158,8 @@ impl QuoteParser {
                                     line_number
                                 ),
                                 source_position: SourcePosition::new(line_number, offset + 1),
+                                span: None,
+                                help: Some("Start every line of the quote with '>'".to_string()),
                             });
                         } else {
                             break;
@@ -178,8 +176,6 @@ impl QuoteParser {
                 indent,
                 offset,
             });
-
-            line_number += 1;
         }
 
         Ok(result)
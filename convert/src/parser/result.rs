@@ -1,4 +1,4 @@
-use crate::util::SourcePosition;
+use crate::util::{SourcePosition, SourceSpan};
 
 pub(crate) type ParseResult<T> = Result<T, ParseError>;
 
@@ -6,4 +6,10 @@ pub(crate) type ParseResult<T> = Result<T, ParseError>;
 pub(crate) struct ParseError {
     pub message: String,
     pub source_position: SourcePosition,
+
+    /// Span of the erroneous source, if it is known beyond the position.
+    pub span: Option<SourceSpan>,
+
+    /// Optional hint on how to fix the error.
+    pub help: Option<String>,
 }
@@ -1,120 +1,112 @@
 use crate::parser::block::table::{TableBlock, TableCell, TableRow};
 use crate::parser::block::ParsedBlockKind;
 use crate::parser::text::TextParser;
-use crate::parser::{ParseResult, ParsedBlock};
-use crate::util::{SourcePosition, SourceSpan};
+use crate::parser::{ParseError, ParseResult, ParsedBlock};
+use crate::util::{
+    is_delimiter_cell, split_table_row, Alignment, SourcePosition, SourceSpan, TableRowCell,
+};
 
 pub(crate) struct TableParser {
     src: String,
     span: SourceSpan,
-    header_row: TableRow,
-    rows: Vec<TableRow>,
-}
-
-#[derive(Copy, Clone)]
-enum RowKind {
-    Header,
-    HeaderSeparator,
-    Body,
 }
 
 impl TableParser {
     pub fn new(src: String, span: SourceSpan) -> Self {
-        Self {
-            src,
-            span,
-            header_row: Vec::new(),
-            rows: Vec::new(),
-        }
+        Self { src, span }
     }
 
-    pub fn parse(mut self) -> ParseResult<ParsedBlock> {
-        let src = self.src.clone();
-
-        for (row_index, line) in src.lines().enumerate() {
-            let line_number = self.span.start.line + row_index;
-
-            let mut started_row = false;
-            let mut offset = 1;
-            let mut cell_value_buffer = String::new();
-
-            for c in line.chars() {
-                match c {
-                    '|' => {
-                        if started_row {
-                            self.consume_buffer_and_register_cell(
-                                &mut cell_value_buffer,
-                                line_number,
-                                offset,
-                                row_index,
-                            )?;
-                        } else {
-                            started_row = true;
-                        }
-                    }
-                    '\n' => {
-                        started_row = false;
-                    }
-                    _ => {
-                        cell_value_buffer.push(c);
-                    }
-                }
-
-                offset += 1;
+    pub fn parse(self) -> ParseResult<ParsedBlock> {
+        let mut lines = (self.span.start.line..).zip(self.src.lines());
+
+        let (header_line_number, header_line) = lines.next().unwrap_or((self.span.start.line, ""));
+        let header_cells = split_table_row(header_line);
+
+        let alignments = match lines.next() {
+            Some((line_number, line)) => {
+                self.parse_delimiter_row(line_number, line, header_cells.len())?
             }
-        }
+            None => {
+                return Err(ParseError {
+                    message: "Expected a delimiter row below the table header".to_string(),
+                    source_position: self.span.end.clone(),
+                    span: None,
+                    help: Some(
+                        "Separate the table header from its rows by a row like '| --- | --- |'"
+                            .to_string(),
+                    ),
+                })
+            }
+        };
+
+        let header_row = self.create_row(header_line_number, header_cells)?;
+        let rows = lines
+            .map(|(line_number, line)| self.create_row(line_number, split_table_row(line)))
+            .collect::<ParseResult<Vec<TableRow>>>()?;
 
         Ok(ParsedBlock::new(
-            ParsedBlockKind::Table(TableBlock::new(self.header_row, self.rows)),
+            ParsedBlockKind::Table(TableBlock::new(header_row, rows).with_alignments(alignments)),
             self.span,
         ))
     }
 
-    fn consume_buffer_and_register_cell(
-        &mut self,
-        cell_value_buffer: &mut String,
+    /// Parse the alignment of each column from the delimiter row below the header row.
+    fn parse_delimiter_row(
+        &self,
         line_number: usize,
-        offset: usize,
-        row_index: usize,
-    ) -> ParseResult<()> {
-        let row_kind = RowKind::for_line_index(row_index);
-        if let RowKind::HeaderSeparator = row_kind {
-            cell_value_buffer.clear();
-            return Ok(());
-        }
-
-        let cell = self.create_cell(&cell_value_buffer, line_number, offset)?;
+        line: &str,
+        column_count: usize,
+    ) -> ParseResult<Vec<Option<Alignment>>> {
+        let cells = split_table_row(line);
+        let span = SourceSpan::new(
+            SourcePosition::new(line_number, 1),
+            SourcePosition::new(line_number, line.chars().count() + 1),
+        );
 
-        match row_kind {
-            RowKind::Header => self.header_row.push(cell),
-            RowKind::Body => {
-                let internal_row_index = row_index - 2;
-                if self.rows.len() <= internal_row_index {
-                    self.rows.push(Vec::new());
-                }
+        if let Some(cell) = cells.iter().find(|cell| !is_delimiter_cell(cell.value())) {
+            return Err(ParseError {
+                message: format!("Invalid table delimiter cell '{}'", cell.value()),
+                source_position: span.start.clone(),
+                span: Some(span),
+                help: Some(
+                    "Use dashes optionally preceded or followed by a colon for alignment, like ':---:'"
+                        .to_string(),
+                ),
+            });
+        }
 
-                self.rows.last_mut().unwrap().push(cell)
-            }
-            _ => unreachable!(),
+        if cells.len() != column_count {
+            return Err(ParseError {
+                message: format!(
+                    "Table delimiter row has {} cells while the header row has {}",
+                    cells.len(),
+                    column_count
+                ),
+                source_position: span.start.clone(),
+                span: Some(span),
+                help: Some("Add a delimiter cell for every cell of the header row".to_string()),
+            });
         }
 
-        cell_value_buffer.clear();
-        Ok(())
+        Ok(cells
+            .iter()
+            .map(|cell| parse_alignment(cell.value()))
+            .collect())
     }
 
-    fn create_cell(
-        &self,
-        value: &str,
-        line_number: usize,
-        offset: usize,
-    ) -> ParseResult<TableCell> {
-        let trimmed_value = value.trim();
+    fn create_row(&self, line_number: usize, cells: Vec<TableRowCell>) -> ParseResult<TableRow> {
+        cells
+            .into_iter()
+            .map(|cell| self.create_cell(line_number, cell))
+            .collect()
+    }
 
+    fn create_cell(&self, line_number: usize, cell: TableRowCell) -> ParseResult<TableCell> {
         let span = SourceSpan::new(
-            SourcePosition::new(line_number, offset - value.len()),
-            SourcePosition::new(line_number, offset - (value.len() - trimmed_value.len())),
+            SourcePosition::new(line_number, cell.start_column()),
+            SourcePosition::new(line_number, cell.end_column()),
         );
-        let text_parser = TextParser::new(trimmed_value.to_string(), span);
+        let text_parser = TextParser::new(cell.value().to_string(), span);
         let parsed_block = text_parser.parse()?;
         let text_block = if let ParsedBlockKind::Text(text_block) = parsed_block.into_kind() {
             text_block
@@ -127,13 +119,16 @@ impl TableParser {
     }
 }
 
-impl RowKind {
-    fn for_line_index(line_index: usize) -> Self {
-        match line_index {
-            0 => RowKind::Header,
-            1 => RowKind::HeaderSeparator,
-            _ => RowKind::Body,
-        }
+/// Alignment of a column given by its delimiter cell: `:---` is left, `:---:` center and `---:` right aligned.
+fn parse_alignment(delimiter: &str) -> Option<Alignment> {
+    match (
+        delimiter.starts_with(':'),
+        delimiter.len() > 1 && delimiter.ends_with(':'),
+    ) {
+        (true, true) => Some(Alignment::Center),
+        (true, false) => Some(Alignment::Left),
+        (false, true) => Some(Alignment::Right),
+        (false, false) => None,
     }
 }
 
@@ -168,7 +163,7 @@ mod tests {
         assert_eq!(header_row.len(), 2);
 
         assert_eq!(
-            header_row.get(0).unwrap().text_tree().to_string(),
+            header_row.first().unwrap().text_tree().to_string(),
             "- [Root]
   - [Text](Column A)
 "
@@ -186,7 +181,7 @@ mod tests {
         assert_eq!(row_1.len(), 2);
 
         assert_eq!(
-            row_1.get(0).unwrap().text_tree().to_string(),
+            row_1.first().unwrap().text_tree().to_string(),
             "- [Root]
   - [Text](1)
 "
@@ -202,7 +197,7 @@ mod tests {
         assert_eq!(row_2.len(), 2);
 
         assert_eq!(
-            row_2.get(0).unwrap().text_tree().to_string(),
+            row_2.first().unwrap().text_tree().to_string(),
             "- [Root]
   - [Text](3)
 "
@@ -240,7 +235,7 @@ mod tests {
         assert_eq!(header_row.len(), 2);
 
         assert_eq!(
-            header_row.get(0).unwrap().text_tree().to_string(),
+            header_row.first().unwrap().text_tree().to_string(),
             "- [Root]
   - [Text](Column )
   - [Italic]
@@ -262,7 +257,7 @@ mod tests {
         assert_eq!(row_1.len(), 2);
 
         assert_eq!(
-            row_1.get(0).unwrap().text_tree().to_string(),
+            row_1.first().unwrap().text_tree().to_string(),
             "- [Root]
   - [Text](1)
 "
@@ -281,7 +276,7 @@ mod tests {
         assert_eq!(row_2.len(), 2);
 
         assert_eq!(
-            row_2.get(0).unwrap().text_tree().to_string(),
+            row_2.first().unwrap().text_tree().to_string(),
             "- [Root]
   - [Text](3)
 "
@@ -293,4 +288,167 @@ mod tests {
 "
         );
     }
+
+    #[test]
+    fn should_not_split_cells_at_escaped_pipes() {
+        let src = "| Operator | Meaning |
+| --- | --- |
+| `a \\| b` | a or b \\| c |";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(3, 28));
+        let parser = TableParser::new(src.to_string(), span);
+
+        let table_block =
+            if let ParsedBlockKind::Table(table_block) = parser.parse().unwrap().into_kind() {
+                table_block
+            } else {
+                panic!("Expected table block");
+            };
+
+        let row = table_block.get_row(0).unwrap();
+        assert_eq!(row.len(), 2);
+        assert_eq!(
+            row.get(1).unwrap().text_tree().to_string(),
+            "- [Root]
+  - [Text](a or b | c)
+"
+        );
+    }
+
+    #[test]
+    fn should_parse_column_alignments() {
+        let src = "| Default | Left | Center | Right |
+| --- | :--- | :---: | ---: |
+| 1 | 2 | 3 | 4 |";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(3, 18));
+        let parser = TableParser::new(src.to_string(), span);
+
+        let table_block =
+            if let ParsedBlockKind::Table(table_block) = parser.parse().unwrap().into_kind() {
+                table_block
+            } else {
+                panic!("Expected table block");
+            };
+
+        assert_eq!(table_block.alignment(0), None);
+        assert_eq!(table_block.alignment(1), Some(Alignment::Left));
+        assert_eq!(table_block.alignment(2), Some(Alignment::Center));
+        assert_eq!(table_block.alignment(3), Some(Alignment::Right));
+        assert_eq!(table_block.alignment(4), None);
+    }
+
+    fn parse_table(src: &str) -> ParseResult<TableBlock> {
+        let line_count = src.lines().count();
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(line_count, 1));
+
+        TableParser::new(src.to_string(), span)
+            .parse()
+            .map(|parsed_block| match parsed_block.into_kind() {
+                ParsedBlockKind::Table(table_block) => table_block,
+                _ => panic!("Expected table block"),
+            })
+    }
+
+    #[test]
+    fn should_parse_table_without_outer_pipes() {
+        let table_block = parse_table(
+            "Name | Age
+--- | ---:
+Max | 42",
+        )
+        .unwrap();
+
+        assert_eq!(table_block.column_count(), 2);
+        assert_eq!(table_block.alignment(1), Some(Alignment::Right));
+        assert_eq!(
+            table_block
+                .get_row(0)
+                .unwrap()
+                .get(1)
+                .unwrap()
+                .text_tree()
+                .to_string(),
+            "- [Root]
+  - [Text](42)
+"
+        );
+    }
+
+    #[test]
+    fn should_not_split_cells_at_pipes_in_inline_code() {
+        let table_block = parse_table(
+            "| Operator | Meaning |
+| --- | --- |
+| `a|b` | a or b |",
+        )
+        .unwrap();
+
+        let row = table_block.get_row(0).unwrap();
+        assert_eq!(row.len(), 2);
+        assert_eq!(
+            row.first().unwrap().text_tree().to_string(),
+            "- [Root]
+  - [Code]
+    - [Text](a|b)
+"
+        );
+    }
+
+    #[test]
+    fn should_keep_rows_with_missing_or_extra_cells() {
+        let table_block = parse_table(
+            "| A | B |
+| - | - |
+| 1 |
+| 1 | 2 | 3 |",
+        )
+        .unwrap();
+
+        assert_eq!(table_block.column_count(), 2);
+        assert_eq!(table_block.get_row(0).unwrap().len(), 1);
+        assert_eq!(table_block.get_row(1).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn should_report_malformed_delimiter_row_with_its_span() {
+        let error = parse_table(
+            "| A | B |
+| --- | -x- |
+| 1 | 2 |",
+        )
+        .unwrap_err();
+
+        assert_eq!(error.message, "Invalid table delimiter cell '-x-'");
+        assert_eq!(
+            error.span,
+            Some(SourceSpan::new(
+                SourcePosition::new(2, 1),
+                SourcePosition::new(2, 14)
+            ))
+        );
+    }
+
+    #[test]
+    fn should_report_delimiter_row_not_matching_header_row() {
+        let error = parse_table(
+            "| A | B |
+| --- |",
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.message,
+            "Table delimiter row has 1 cells while the header row has 2"
+        );
+        assert_eq!(error.source_position, SourcePosition::new(2, 1));
+    }
+
+    #[test]
+    fn should_report_missing_delimiter_row() {
+        let error = parse_table("| A | B |").unwrap_err();
+
+        assert_eq!(
+            error.message,
+            "Expected a delimiter row below the table header"
+        );
+    }
 }
@@ -1,6 +1,7 @@
 use TokenKind::{
-    BoldEnd, BoldStart, CodeEnd, CodeStart, Error, Function, Image, ItalicEnd, ItalicStart, Link,
-    Text,
+    BoldEnd, BoldStart, CodeEnd, CodeStart, Error, FootnoteReference, Function, Image,
+    ImageReference, ItalicEnd, ItalicStart, LineBreak, Link, LinkReference, StrikethroughEnd,
+    StrikethroughStart, Text,
 };
 
 pub(crate) use crate::parser::block::text::{TextBlock, TextNodeKind, TextTree};
@@ -28,10 +29,12 @@ impl TextParser {
 
     pub fn parse(mut self) -> ParseResult<ParsedBlock> {
         let mut parent_node_id_stack = vec![self.tree.root().id()];
+        let block_span = self.tree.root().span().clone();
 
         for token in self.tokenizer {
             let parent_node_id = *parent_node_id_stack.last().unwrap();
-            let span = token.span().clone();
+            // Trailing line endings must not let the nodes extend past the block
+            let span = token.span().clamped_to(&block_span);
 
             match token.kind() {
                 Error {
@@ -41,6 +44,8 @@ impl TextParser {
                     return Err(ParseError {
                         message: message.clone(),
                         source_position: source_position.clone(),
+                        span: None,
+                        help: None,
                     });
                 }
                 Text(s) => {
@@ -50,11 +55,16 @@ impl TextParser {
                         span,
                     );
                 }
-                Link { label, target } => {
+                Link {
+                    label,
+                    target,
+                    title,
+                } => {
                     let node_id = self.tree.register_node(
                         parent_node_id,
                         TextNodeKind::Link {
                             target: target.clone(),
+                            title: title.clone(),
                         },
                         span.clone(),
                     );
@@ -64,10 +74,13 @@ impl TextParser {
                         span,
                     );
                 }
-                Image { label, src } => {
+                Image { label, src, title } => {
                     let node_id = self.tree.register_node(
                         parent_node_id,
-                        TextNodeKind::Image { src: src.clone() },
+                        TextNodeKind::Image {
+                            src: src.clone(),
+                            title: title.clone(),
+                        },
                         span.clone(),
                     );
                     self.tree.register_node(
@@ -76,6 +89,36 @@ impl TextParser {
                         span,
                     );
                 }
+                LinkReference {
+                    label,
+                    reference,
+                    src,
+                }
+                | ImageReference {
+                    label,
+                    reference,
+                    src,
+                } => {
+                    let node_kind = match token.kind() {
+                        LinkReference { .. } => TextNodeKind::LinkReference {
+                            reference: reference.clone(),
+                            src: src.clone(),
+                        },
+                        _ => TextNodeKind::ImageReference {
+                            reference: reference.clone(),
+                            src: src.clone(),
+                        },
+                    };
+
+                    let node_id = self
+                        .tree
+                        .register_node(parent_node_id, node_kind, span.clone());
+                    self.tree.register_node(
+                        node_id,
+                        TextNodeKind::Text { src: label.clone() },
+                        span,
+                    );
+                }
                 Function { name, parameters } => {
                     self.tree.register_node(
                         parent_node_id,
@@ -86,11 +129,25 @@ impl TextParser {
                         span,
                     );
                 }
-                BoldStart | ItalicStart | CodeStart => {
+                FootnoteReference { label } => {
+                    self.tree.register_node(
+                        parent_node_id,
+                        TextNodeKind::FootnoteReference {
+                            label: label.clone(),
+                        },
+                        span,
+                    );
+                }
+                LineBreak => {
+                    self.tree
+                        .register_node(parent_node_id, TextNodeKind::LineBreak, span);
+                }
+                BoldStart | ItalicStart | CodeStart | StrikethroughStart => {
                     let node_kind = match token.kind() {
                         BoldStart => TextNodeKind::Bold,
                         ItalicStart => TextNodeKind::Italic,
                         CodeStart => TextNodeKind::Code,
+                        StrikethroughStart => TextNodeKind::Strikethrough,
                         _ => unreachable!(),
                     };
 
@@ -98,7 +155,7 @@ impl TextParser {
 
                     parent_node_id_stack.push(node_id);
                 }
-                BoldEnd | ItalicEnd | CodeEnd => {
+                BoldEnd | ItalicEnd | CodeEnd | StrikethroughEnd => {
                     parent_node_id_stack.pop();
                 }
             }
@@ -224,6 +281,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_parse_text_block_with_strikethrough() {
+        let src = "This is ~~struck~~ and ~~struck **bold**~~ and **bold ~~struck~~**.";
+        let span = SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 68));
+
+        let parser = TextParser::new(src.to_string(), span.clone());
+        let result = parser.parse();
+
+        assert!(result.is_ok());
+
+        let parsed_block = result.unwrap();
+        assert!(parsed_block.is_text());
+
+        let tree = if let ParsedBlockKind::Text(b) = parsed_block.kind() {
+            b.tree()
+        } else {
+            panic!("Expected text block");
+        };
+
+        assert_eq!(
+            format!("{}", tree),
+            "- [Root]
+  - [Text](This is )
+  - [Strikethrough]
+    - [Text](struck)
+  - [Text]( and )
+  - [Strikethrough]
+    - [Text](struck )
+    - [Bold]
+      - [Text](bold)
+  - [Text]( and )
+  - [Bold]
+    - [Text](bold )
+    - [Strikethrough]
+      - [Text](struck)
+  - [Text](.)
+"
+        );
+    }
+
     #[test]
     fn should_parse_text_block_with_link() {
         let src = "This is a **[link](https://example.com)**.";
@@ -1,3 +1,4 @@
+use crate::parser::block::footnote::FootnoteLabel;
 use crate::parser::block::function::{FunctionName, FunctionParameters};
 use crate::util::{SourcePosition, SourceSpan};
 
@@ -17,21 +18,40 @@ pub(crate) enum TokenKind {
     Link {
         label: String,
         target: String,
+        title: Option<String>,
     },
     Image {
         label: String,
         src: String,
+        title: Option<String>,
+    },
+    /// Reference-style link like `[label][reference]`, whose source is kept if the reference is not defined.
+    LinkReference {
+        label: String,
+        reference: String,
+        src: String,
+    },
+    ImageReference {
+        label: String,
+        reference: String,
+        src: String,
     },
     Function {
         name: FunctionName,
         parameters: FunctionParameters,
     },
+    FootnoteReference {
+        label: FootnoteLabel,
+    },
     BoldStart,
     BoldEnd,
     ItalicStart,
     ItalicEnd,
     CodeStart,
     CodeEnd,
+    StrikethroughStart,
+    StrikethroughEnd,
+    LineBreak,
 }
 
 impl Token {
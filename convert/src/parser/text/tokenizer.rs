@@ -1,11 +1,16 @@
 use std::collections::{HashMap, VecDeque};
 
+use crate::categorizer::is_footnote_label;
 use crate::parser::text::token::TokenKind::{
-    BoldEnd, BoldStart, CodeEnd, CodeStart, Error, Function, Image, ItalicEnd, ItalicStart, Link,
-    Text,
+    BoldEnd, BoldStart, CodeEnd, CodeStart, Error, FootnoteReference, Function, Image,
+    ImageReference, ItalicEnd, ItalicStart, LineBreak, Link, LinkReference, StrikethroughEnd,
+    StrikethroughStart, Text,
 };
 use crate::parser::text::token::{Token, TokenKind};
-use crate::util::{SourcePosition, SourceSpan};
+use crate::util::{
+    decode_entity, is_escapable, split_link_destination, unescape, SourcePosition, SourceSpan,
+    MAX_ENTITY_LENGTH,
+};
 
 const MAX_SOURCE_POSITION_UPDATE_HISTORY_SIZE: usize = 100;
 
@@ -26,6 +31,13 @@ enum SourcePositionUpdate {
     Ignore,
 }
 
+/// Link recognized in the text itself, ending at the given look ahead offset.
+struct Autolink {
+    label: String,
+    target: String,
+    end: usize,
+}
+
 #[derive(Debug, Clone)]
 struct FutureToken {
     token_kind: TokenKind,
@@ -138,6 +150,249 @@ impl Tokenizer {
         None
     }
 
+    /// Check whether the line ending at the current offset ends with two or more spaces,
+    /// which force a line break unless it is the last line.
+    fn is_hard_line_break(&self) -> bool {
+        let trailing_spaces = self.src[..self.byte_offset()]
+            .trim_end_matches('\r')
+            .chars()
+            .rev()
+            .take_while(|c| *c == ' ')
+            .count();
+
+        trailing_spaces >= 2 && self.is_followed_by_text(1)
+    }
+
+    /// Check whether there is anything but whitespace from the given look ahead offset on.
+    fn is_followed_by_text(&self, start_at: usize) -> bool {
+        self.src
+            .chars()
+            .skip(self.offset + start_at)
+            .any(|c| !c.is_whitespace())
+    }
+
+    /// Byte offset of the current char in the source.
+    fn byte_offset(&self) -> usize {
+        self.src
+            .char_indices()
+            .nth(self.offset)
+            .map_or(self.src.len(), |(offset, _)| offset)
+    }
+
+    /// Create a line break token for the line ending at the current offset,
+    /// skipping the indentation of the next line.
+    fn line_break_token(&mut self, start_position: SourcePosition) -> Token {
+        while matches!(self.look_ahead(1), Some(' ' | '\t')) {
+            self.ignore_next_chars(1);
+        }
+
+        Token::new(
+            LineBreak,
+            SourceSpan::new(start_position, self.offset_source_position()),
+        )
+    }
+
+    /// Read the source from the current char up to and including the given look ahead offset.
+    fn read_source(&self, end: usize) -> String {
+        (0..=end)
+            .filter_map(|offset| self.look_ahead(offset))
+            .collect()
+    }
+
+    /// Read the chars starting at the given look ahead offset up to the given closing char,
+    /// which does not count if escaped by a backslash.
+    /// Returns the chars read and the look ahead offset of the closing char (or the end of the source).
+    fn read_until(&self, start_at: usize, closing: char) -> (String, usize) {
+        let mut count = start_at;
+        let mut result = String::new();
+        while let Some(next_char) = self.look_ahead(count) {
+            if next_char == closing {
+                break;
+            }
+
+            result.push(next_char);
+            if next_char == '\\' {
+                if let Some(escaped) = self.look_ahead(count + 1).filter(|c| is_escapable(*c)) {
+                    result.push(escaped);
+                    count += 1;
+                }
+            }
+
+            count += 1;
+        }
+
+        (result, count)
+    }
+
+    /// Find a reference like `[ref]` or `[]` starting at the given look ahead offset,
+    /// as it follows the label of a reference-style link or image.
+    /// Returns the reference (`None` for `[]`, which refers to the label) and the offset of the closing ']'.
+    fn find_reference(&self, start_at: usize) -> Option<(Option<String>, usize)> {
+        if self.look_ahead(start_at) != Some('[') {
+            return None;
+        }
+
+        let mut count = start_at + 1;
+        let mut reference = String::new();
+        while let Some(next_char) = self.look_ahead(count) {
+            match next_char {
+                ']' if reference.trim().is_empty() => return Some((None, count)),
+                ']' => return Some((Some(reference), count)),
+                '[' => return None,
+                _ => reference.push(next_char),
+            }
+
+            count += 1;
+        }
+
+        None
+    }
+
+    /// Find an autolink like `<https://example.com>` or `<mail@example.com>` starting at the current offset.
+    fn find_autolink(&self) -> Option<Autolink> {
+        let mut count = 1;
+        let mut label = String::new();
+        loop {
+            match self.look_ahead(count)? {
+                '>' => break,
+                '<' => return None,
+                c if c.is_whitespace() => return None,
+                c => label.push(c),
+            }
+
+            count += 1;
+        }
+
+        let target = if is_uri(&label) {
+            label.clone()
+        } else if is_email_address(&label) {
+            format!("mailto:{}", label)
+        } else {
+            return None;
+        };
+
+        Some(Autolink {
+            label,
+            target,
+            end: count,
+        })
+    }
+
+    /// Find a bare URL like `https://example.com` or `www.example.com` or an e-mail address
+    /// starting at the current offset, which must be at the start of a word.
+    /// Trailing punctuation is not considered part of the link.
+    fn find_bare_link(&self) -> Option<Autolink> {
+        let previous_char = self
+            .offset
+            .checked_sub(1)
+            .and_then(|offset| self.read_at(offset));
+        let is_word_start = match previous_char {
+            Some(c) => c.is_whitespace() || matches!(c, '*' | '~' | '('),
+            None => true,
+        };
+        if !is_word_start {
+            return None;
+        }
+
+        let mut count = 0;
+        let mut candidate = String::new();
+        while let Some(c) = self.look_ahead(count) {
+            if c.is_whitespace() || c == '<' {
+                break;
+            }
+
+            candidate.push(c);
+            count += 1;
+        }
+
+        let label = trim_trailing_punctuation(&candidate);
+        let target = if label.starts_with("https://") || label.starts_with("http://") {
+            let (_, rest) = label.split_once("://").unwrap();
+            if !is_domain(domain_of(rest)) {
+                return None;
+            }
+            label.to_string()
+        } else if label.starts_with("www.") {
+            if !is_domain(domain_of(label)) {
+                return None;
+            }
+            format!("http://{}", label)
+        } else {
+            // E-mail addresses end with their domain, so anything following it is not part of the link
+            let length = email_address_length(label)?;
+            let label = &label[..length];
+            return Some(Autolink {
+                label: label.to_string(),
+                target: format!("mailto:{}", label),
+                end: label.chars().count() - 1,
+            });
+        };
+
+        Some(Autolink {
+            label: label.to_string(),
+            target,
+            end: label.chars().count() - 1,
+        })
+    }
+
+    /// Create the token for the given autolink, or the text token for the text in front of it.
+    fn autolink_token(
+        &mut self,
+        text_buffer: &str,
+        start_position: SourcePosition,
+        autolink: Autolink,
+    ) -> Token {
+        if !text_buffer.is_empty() {
+            self.mark_char_as_unconsumed();
+
+            return Token::new(
+                Text(text_buffer.to_string()),
+                SourceSpan::new(start_position, self.offset_source_position()),
+            );
+        }
+
+        self.ignore_next_chars(autolink.end);
+        Token::new(
+            Link {
+                label: autolink.label,
+                target: autolink.target,
+                title: None,
+            },
+            SourceSpan::new(start_position, self.offset_source_position()),
+        )
+    }
+
+    /// Find the offset of the '~~' closing the strikethrough starting at the current offset.
+    fn find_strikethrough_end(&mut self) -> Option<usize> {
+        let mut count = 2;
+        let mut in_code_emphasis = false;
+        while let Some(next_char) = self.look_ahead(count) {
+            if in_code_emphasis {
+                if next_char == '`' {
+                    in_code_emphasis = false;
+                }
+                count += 1;
+                continue;
+            }
+
+            match next_char {
+                '\\' => count += 1, // Ignore the escaped char
+                '`' => {
+                    in_code_emphasis = self.find_next_char_matching('`', count).is_some();
+                }
+                // Strikethrough must not be empty
+                '~' if count > 2 && self.look_ahead(count + 1) == Some('~') => {
+                    return Some(self.offset + count);
+                }
+                _ => {}
+            }
+
+            count += 1;
+        }
+
+        None
+    }
+
     fn find_formatting_pair(&mut self) -> Option<Vec<FutureToken>> {
         let mut is_italic = true;
         let mut is_bold = false;
@@ -199,7 +454,6 @@ impl Tokenizer {
         };
 
         let mut count = 2;
-        let mut ignore_next_star = false;
         while let Some(next_char) = self.look_ahead(count + 1) {
             if in_code_emphasis {
                 if next_char == '`' {
@@ -210,20 +464,14 @@ impl Tokenizer {
             }
 
             match next_char {
-                '\\' => ignore_next_star = true,
+                '\\' => count += 1, // Ignore the escaped char
                 '`' => {
                     let count = self.find_next_char_matching('`', count + 1);
-                    if let Some(_) = count {
+                    if count.is_some() {
                         in_code_emphasis = true;
                     }
                 }
                 '*' => {
-                    if ignore_next_star {
-                        ignore_next_star = false;
-                        count += 1;
-                        continue;
-                    }
-
                     if is_italic && is_bold {
                         // Cannot be another "more formatting" than there already is -> must be a closing star
                         let next_char_1 = self.look_ahead(count + 2);
@@ -373,11 +621,7 @@ impl Tokenizer {
                         }
                     }
                 }
-                _ => {
-                    if ignore_next_star {
-                        ignore_next_star = false;
-                    }
-                }
+                _ => {}
             }
 
             count += 1;
@@ -429,6 +673,9 @@ impl Iterator for Tokenizer {
                                     self.offset_source_position().clone(),
                                 ),
                             ));
+                        } else if c == '\n' {
+                            // Line endings in inline code are folded to spaces
+                            text_buffer.push(' ');
                         } else {
                             text_buffer.push(c);
                         }
@@ -436,29 +683,75 @@ impl Iterator for Tokenizer {
                     }
 
                     match c {
-                        '\\' => {
-                            treat_next_special_char_as_text = true;
+                        '\\' => match self.look_ahead(1) {
+                            Some(next_char) if is_escapable(next_char) => {
+                                treat_next_special_char_as_text = true;
+                            }
+                            Some('\r' | '\n') if self.is_followed_by_text(1) => {
+                                if !text_buffer.is_empty() {
+                                    self.mark_char_as_unconsumed();
+
+                                    return Some(Token::new(
+                                        Text(text_buffer.to_string()),
+                                        SourceSpan::new(
+                                            start_position.clone(),
+                                            self.offset_source_position().clone(),
+                                        ),
+                                    ));
+                                }
+
+                                self.ignore_next_chars(1);
+                                return Some(self.line_break_token(start_position));
+                            }
+                            _ => text_buffer.push(c),
+                        },
+                        '&' => {
+                            let candidate = (0..MAX_ENTITY_LENGTH)
+                                .map_while(|count| self.look_ahead(count))
+                                .collect::<String>();
+
+                            match decode_entity(&candidate) {
+                                Some((decoded, length)) => {
+                                    text_buffer.push(decoded);
+                                    self.ignore_next_chars(length - 1);
+                                }
+                                None => text_buffer.push(c),
+                            }
                         }
                         ' ' | '\t' => {
                             text_buffer.push(' ');
                         }
+                        '\n' if self.is_hard_line_break() => {
+                            // Trailing spaces forcing the line break are not part of the text
+                            let text = text_buffer.trim_end_matches(' ');
+                            if !text.is_empty() {
+                                self.mark_char_as_unconsumed();
+
+                                return Some(Token::new(
+                                    Text(text.to_string()),
+                                    SourceSpan::new(
+                                        start_position.clone(),
+                                        self.offset_source_position().clone(),
+                                    ),
+                                ));
+                            }
+
+                            return Some(self.line_break_token(start_position));
+                        }
                         '\n' => {
-                            if text_buffer.chars().last() != Some(' ') {
+                            // A line ending at the end of the block is no soft break
+                            if self.is_followed_by_text(1) && !text_buffer.ends_with(' ') {
                                 text_buffer.push(' ');
                             }
                         }
                         '#' => {
                             let mut count = 1;
                             let mut function_name = String::new();
-                            loop {
-                                if let Some(next_char) = self.look_ahead(count) {
-                                    if next_char == '(' {
-                                        break;
-                                    } else {
-                                        function_name.push(next_char);
-                                    }
-                                } else {
+                            while let Some(next_char) = self.look_ahead(count) {
+                                if next_char == '(' {
                                     break;
+                                } else {
+                                    function_name.push(next_char);
                                 }
 
                                 count += 1;
@@ -475,46 +768,42 @@ impl Iterator for Tokenizer {
                             let mut parameter_value = String::new();
                             let mut is_in_arg_name = true;
 
-                            loop {
-                                if let Some(next_char) = self.look_ahead(count) {
-                                    match next_char {
-                                        ')' => {
-                                            if !parameter_name.trim().is_empty() {
-                                                parameters.insert(
-                                                    parameter_name.trim().to_string(),
-                                                    parameter_value.trim().to_string(),
-                                                );
-                                            }
-                                            break;
+                            while let Some(next_char) = self.look_ahead(count) {
+                                match next_char {
+                                    ')' => {
+                                        if !parameter_name.trim().is_empty() {
+                                            parameters.insert(
+                                                parameter_name.trim().to_string(),
+                                                parameter_value.trim().to_string(),
+                                            );
                                         }
-                                        ',' => {
-                                            if !parameter_value.trim().is_empty() {
-                                                parameters.insert(
-                                                    parameter_name.trim().to_string(),
-                                                    parameter_value.trim().to_string(),
-                                                );
-                                            }
-                                            parameter_name.clear();
-                                            parameter_value.clear();
-                                            is_in_arg_name = true;
+                                        break;
+                                    }
+                                    ',' => {
+                                        if !parameter_value.trim().is_empty() {
+                                            parameters.insert(
+                                                parameter_name.trim().to_string(),
+                                                parameter_value.trim().to_string(),
+                                            );
                                         }
-                                        ':' => {
-                                            if is_in_arg_name {
-                                                is_in_arg_name = false;
-                                            } else {
-                                                parameter_value.push(next_char);
-                                            }
+                                        parameter_name.clear();
+                                        parameter_value.clear();
+                                        is_in_arg_name = true;
+                                    }
+                                    ':' => {
+                                        if is_in_arg_name {
+                                            is_in_arg_name = false;
+                                        } else {
+                                            parameter_value.push(next_char);
                                         }
-                                        _ => {
-                                            if is_in_arg_name {
-                                                parameter_name.push(next_char);
-                                            } else {
-                                                parameter_value.push(next_char);
-                                            }
+                                    }
+                                    _ => {
+                                        if is_in_arg_name {
+                                            parameter_name.push(next_char);
+                                        } else {
+                                            parameter_value.push(next_char);
                                         }
                                     }
-                                } else {
-                                    break;
                                 }
 
                                 count += 1;
@@ -546,11 +835,7 @@ impl Iterator for Tokenizer {
                         }
                         '!' => {
                             // Check if next char is '['
-                            let may_be_image = if let Some('[') = self.look_ahead(1) {
-                                true
-                            } else {
-                                false
-                            };
+                            let may_be_image = matches!(self.look_ahead(1), Some('['));
                             if !may_be_image {
                                 text_buffer.push(c);
                                 continue;
@@ -558,20 +843,8 @@ impl Iterator for Tokenizer {
 
                             // Find label
                             let mut count = 2;
-                            let mut label = String::new();
-                            loop {
-                                if let Some(next_char) = self.look_ahead(count) {
-                                    if next_char == ']' {
-                                        break;
-                                    } else {
-                                        label.push(next_char);
-                                    }
-                                } else {
-                                    break;
-                                }
-
-                                count += 1;
-                            }
+                            let (label, end) = self.read_until(count, ']');
+                            count = end;
 
                             if label.is_empty() {
                                 text_buffer.push(c);
@@ -581,27 +854,85 @@ impl Iterator for Tokenizer {
                             // Find src
                             let is_opening_parenthesis = self.look_ahead(count + 1) == Some('(');
                             if !is_opening_parenthesis {
+                                let (reference, end) = match self.find_reference(count + 1) {
+                                    Some(found) => found,
+                                    None => {
+                                        text_buffer.push(c);
+                                        continue;
+                                    }
+                                };
+
+                                if !text_buffer.is_empty() {
+                                    self.mark_char_as_unconsumed();
+
+                                    return Some(Token::new(
+                                        Text(text_buffer.to_string()),
+                                        SourceSpan::new(
+                                            start_position.clone(),
+                                            self.offset_source_position().clone(),
+                                        ),
+                                    ));
+                                }
+
+                                let src = self.read_source(end);
+                                self.ignore_next_chars(end);
+                                return Some(Token::new(
+                                    ImageReference {
+                                        reference: reference.unwrap_or_else(|| label.clone()),
+                                        label: unescape(&label),
+                                        src,
+                                    },
+                                    SourceSpan::new(
+                                        start_position.clone(),
+                                        self.offset_source_position().clone(),
+                                    ),
+                                ));
+                            }
+
+                            count += 2;
+                            let (src, end) = self.read_until(count, ')');
+                            count = end;
+
+                            if src.is_empty() {
                                 text_buffer.push(c);
                                 continue;
                             }
 
-                            count += 2;
-                            let mut src = String::new();
-                            loop {
-                                if let Some(next_char) = self.look_ahead(count) {
-                                    if next_char == ')' {
-                                        break;
-                                    } else {
-                                        src.push(next_char);
-                                    }
-                                } else {
-                                    break;
-                                }
+                            if !text_buffer.is_empty() {
+                                self.mark_char_as_unconsumed();
 
-                                count += 1;
+                                return Some(Token::new(
+                                    Text(text_buffer.to_string()),
+                                    SourceSpan::new(
+                                        start_position.clone(),
+                                        self.offset_source_position().clone(),
+                                    ),
+                                ));
                             }
 
-                            if src.is_empty() {
+                            let (src, title) = split_target(src);
+
+                            self.ignore_next_chars(count);
+                            return Some(Token::new(
+                                Image {
+                                    label: unescape(&label),
+                                    src,
+                                    title,
+                                },
+                                SourceSpan::new(
+                                    start_position.clone(),
+                                    self.offset_source_position().clone(),
+                                ),
+                            ));
+                        }
+                        '[' if self.look_ahead(1) == Some('^') => {
+                            // Find footnote label
+                            let mut count = 2;
+                            let (label, end) = self.read_until(count, ']');
+                            count = end;
+
+                            let is_closed = self.look_ahead(count) == Some(']');
+                            if !is_closed || !is_footnote_label(&label) {
                                 text_buffer.push(c);
                                 continue;
                             }
@@ -620,7 +951,7 @@ impl Iterator for Tokenizer {
 
                             self.ignore_next_chars(count);
                             return Some(Token::new(
-                                Image { label, src },
+                                FootnoteReference { label },
                                 SourceSpan::new(
                                     start_position.clone(),
                                     self.offset_source_position().clone(),
@@ -630,20 +961,8 @@ impl Iterator for Tokenizer {
                         '[' => {
                             // Find label
                             let mut count = 1;
-                            let mut label = String::new();
-                            loop {
-                                if let Some(next_char) = self.look_ahead(count) {
-                                    if next_char == ']' {
-                                        break;
-                                    } else {
-                                        label.push(next_char);
-                                    }
-                                } else {
-                                    break;
-                                }
-
-                                count += 1;
-                            }
+                            let (label, end) = self.read_until(count, ']');
+                            count = end;
 
                             if label.is_empty() {
                                 text_buffer.push(c);
@@ -653,26 +972,45 @@ impl Iterator for Tokenizer {
                             // Find target
                             let is_opening_parenthesis = self.look_ahead(count + 1) == Some('(');
                             if !is_opening_parenthesis {
-                                text_buffer.push(c);
-                                continue;
-                            }
-
-                            count += 2;
-                            let mut target = String::new();
-                            loop {
-                                if let Some(next_char) = self.look_ahead(count) {
-                                    if next_char == ')' {
-                                        break;
-                                    } else {
-                                        target.push(next_char);
+                                let (reference, end) = match self.find_reference(count + 1) {
+                                    Some(found) => found,
+                                    None => {
+                                        text_buffer.push(c);
+                                        continue;
                                     }
-                                } else {
-                                    break;
+                                };
+
+                                if !text_buffer.is_empty() {
+                                    self.mark_char_as_unconsumed();
+
+                                    return Some(Token::new(
+                                        Text(text_buffer.to_string()),
+                                        SourceSpan::new(
+                                            start_position.clone(),
+                                            self.offset_source_position().clone(),
+                                        ),
+                                    ));
                                 }
 
-                                count += 1;
+                                let src = self.read_source(end);
+                                self.ignore_next_chars(end);
+                                return Some(Token::new(
+                                    LinkReference {
+                                        reference: reference.unwrap_or_else(|| label.clone()),
+                                        label: unescape(&label),
+                                        src,
+                                    },
+                                    SourceSpan::new(
+                                        start_position.clone(),
+                                        self.offset_source_position().clone(),
+                                    ),
+                                ));
                             }
 
+                            count += 2;
+                            let (target, end) = self.read_until(count, ')');
+                            count = end;
+
                             if target.is_empty() {
                                 text_buffer.push(c);
                                 continue;
@@ -690,9 +1028,15 @@ impl Iterator for Tokenizer {
                                 ));
                             }
 
+                            let (target, title) = split_target(target);
+
                             self.ignore_next_chars(count);
                             return Some(Token::new(
-                                Link { label, target },
+                                Link {
+                                    label: unescape(&label),
+                                    target,
+                                    title,
+                                },
                                 SourceSpan::new(
                                     start_position.clone(),
                                     self.offset_source_position().clone(),
@@ -774,6 +1118,63 @@ impl Iterator for Tokenizer {
                                 text_buffer.push(c);
                             }
                         }
+                        '~' => {
+                            if self.look_ahead(1) != Some('~') {
+                                text_buffer.push(c);
+                                continue;
+                            }
+
+                            let offset = self.offset;
+                            let is_closing =
+                                self.future_closing_formatting_tokens.iter().any(|token| {
+                                    token.offset == offset && token.token_kind == StrikethroughEnd
+                                });
+                            let closing_offset = if is_closing {
+                                None
+                            } else {
+                                self.find_strikethrough_end()
+                            };
+
+                            if !is_closing && closing_offset.is_none() {
+                                // Not a strikethrough -> keep both tildes as text
+                                text_buffer.push_str("~~");
+                                self.ignore_next_chars(1);
+                                continue;
+                            }
+
+                            if !text_buffer.is_empty() {
+                                self.mark_char_as_unconsumed();
+
+                                return Some(Token::new(
+                                    Text(text_buffer.to_string()),
+                                    SourceSpan::new(
+                                        start_position.clone(),
+                                        self.offset_source_position().clone(),
+                                    ),
+                                ));
+                            }
+
+                            self.ignore_next_chars(1);
+
+                            let token_kind = if let Some(closing_offset) = closing_offset {
+                                self.future_closing_formatting_tokens.push(FutureToken {
+                                    token_kind: StrikethroughEnd,
+                                    offset: closing_offset,
+                                });
+
+                                StrikethroughStart
+                            } else {
+                                self.future_closing_formatting_tokens
+                                    .retain(|token| token.offset != offset);
+
+                                StrikethroughEnd
+                            };
+
+                            return Some(Token::new(
+                                token_kind,
+                                SourceSpan::new(start_position, self.offset_source_position()),
+                            ));
+                        }
                         '`' => {
                             if !text_buffer.is_empty() {
                                 self.mark_char_as_unconsumed();
@@ -811,8 +1212,27 @@ impl Iterator for Tokenizer {
                                 SourceSpan::new(start_position, self.offset_source_position()),
                             ));
                         }
+                        '<' => match self.find_autolink() {
+                            Some(autolink) => {
+                                return Some(self.autolink_token(
+                                    &text_buffer,
+                                    start_position,
+                                    autolink,
+                                ));
+                            }
+                            None => text_buffer.push(c),
+                        },
                         '\r' => {}
-                        _ => text_buffer.push(c),
+                        _ => match self.find_bare_link() {
+                            Some(autolink) => {
+                                return Some(self.autolink_token(
+                                    &text_buffer,
+                                    start_position,
+                                    autolink,
+                                ));
+                            }
+                            None => text_buffer.push(c),
+                        },
                     }
                 }
                 None => {
@@ -830,12 +1250,94 @@ impl Iterator for Tokenizer {
     }
 }
 
+/// Check for an absolute URI with a scheme like `https:` or `irc:`.
+fn is_uri(src: &str) -> bool {
+    let (scheme, _) = match src.split_once(':') {
+        Some(parts) => parts,
+        None => return false,
+    };
+
+    (2..=32).contains(&scheme.len())
+        && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '.' | '-'))
+}
+
+fn is_email_address(src: &str) -> bool {
+    email_address_length(src) == Some(src.len())
+}
+
+/// Length of the e-mail address like `mail@example.com` the given source starts with.
+fn email_address_length(src: &str) -> Option<usize> {
+    let (local_part, rest) = src.split_once('@')?;
+    let is_local_part = !local_part.is_empty()
+        && local_part
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-' | '_'));
+    if !is_local_part {
+        return None;
+    }
+
+    let domain_length = rest
+        .find(|c: char| !c.is_ascii_alphanumeric() && !matches!(c, '.' | '-' | '_'))
+        .unwrap_or(rest.len());
+    let domain = rest[..domain_length].trim_end_matches('.');
+    if !is_domain(domain) || domain.ends_with(['-', '_']) {
+        return None;
+    }
+
+    Some(local_part.len() + 1 + domain.len())
+}
+
+/// Domain part of a URL without its scheme, like `example.com` of `example.com:8080/path?query`.
+fn domain_of(src: &str) -> &str {
+    let end = src.find([':', '/', '?', '#']).unwrap_or(src.len());
+    &src[..end]
+}
+
+fn is_domain(src: &str) -> bool {
+    src.contains('.')
+        && src.split('.').all(|segment| !segment.is_empty())
+        && src
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '.' | '-' | '_'))
+}
+
+/// Strip trailing punctuation that most likely belongs to the surrounding sentence, like the period of
+/// `Visit https://example.com.`, as well as closing parentheses without a matching opening parenthesis.
+fn trim_trailing_punctuation(src: &str) -> &str {
+    let mut src = src;
+    loop {
+        let trimmed = src.trim_end_matches(['?', '!', '.', ',', ':', '*', '_', '~', '\'', '"']);
+        let trimmed = match trimmed.strip_suffix(')') {
+            Some(rest) if trimmed.matches('(').count() < trimmed.matches(')').count() => rest,
+            _ => trimmed,
+        };
+
+        if trimmed.len() == src.len() {
+            return src;
+        }
+        src = trimmed;
+    }
+}
+
+/// Split the optional title off the target of an inline link or image like `[x](url "title")`.
+/// Targets not followed by a proper title are kept as they are.
+fn split_target(target: String) -> (String, Option<String>) {
+    match split_link_destination(&target) {
+        Some((target, title)) => (unescape(target), title.map(unescape)),
+        None => (unescape(&target), None),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
     use crate::parser::text::token::TokenKind::{
-        BoldEnd, BoldStart, CodeEnd, CodeStart, Function, Image, ItalicEnd, ItalicStart, Link, Text,
+        BoldEnd, BoldStart, CodeEnd, CodeStart, FootnoteReference, Function, Image, ItalicEnd,
+        ItalicStart, Link, StrikethroughEnd, StrikethroughStart, Text,
     };
 
     use super::*;
@@ -979,8 +1481,60 @@ lines.";
         assert_eq!(
             tokenizer.next().unwrap(),
             Token::new(
-                BoldEnd,
-                SourceSpan::new(SourcePosition::new(1, 21), SourcePosition::new(1, 23))
+                BoldEnd,
+                SourceSpan::new(SourcePosition::new(1, 21), SourcePosition::new(1, 23))
+            )
+        );
+        assert!(tokenizer.next().is_none());
+    }
+
+    #[test]
+    fn tokenize_strikethrough_emphasis() {
+        let src = "~~This is struck~~";
+
+        let mut tokenizer = Tokenizer::new(
+            src.to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 19)),
+        );
+
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                StrikethroughStart,
+                SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 3))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                Text("This is struck".to_string()),
+                SourceSpan::new(SourcePosition::new(1, 3), SourcePosition::new(1, 17))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                StrikethroughEnd,
+                SourceSpan::new(SourcePosition::new(1, 17), SourcePosition::new(1, 19))
+            )
+        );
+        assert!(tokenizer.next().is_none());
+    }
+
+    #[test]
+    fn tokenize_unmatched_tildes_as_text() {
+        let src = "About ~5 minutes ~~ or more";
+
+        let mut tokenizer = Tokenizer::new(
+            src.to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 28)),
+        );
+
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                Text("About ~5 minutes ~~ or more".to_string()),
+                SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 28))
             )
         );
         assert!(tokenizer.next().is_none());
@@ -1108,6 +1662,92 @@ lines.";
         assert!(tokenizer.next().is_none());
     }
 
+    #[test]
+    fn tokenize_mixed_bold_and_strikethrough_emphasis() {
+        let src = "**bold ~~struck~~ text**";
+
+        let mut tokenizer = Tokenizer::new(
+            src.to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 25)),
+        );
+
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                BoldStart,
+                SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 3))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                Text("bold ".to_string()),
+                SourceSpan::new(SourcePosition::new(1, 3), SourcePosition::new(1, 8))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                StrikethroughStart,
+                SourceSpan::new(SourcePosition::new(1, 8), SourcePosition::new(1, 10))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                Text("struck".to_string()),
+                SourceSpan::new(SourcePosition::new(1, 10), SourcePosition::new(1, 16))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                StrikethroughEnd,
+                SourceSpan::new(SourcePosition::new(1, 16), SourcePosition::new(1, 18))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                Text(" text".to_string()),
+                SourceSpan::new(SourcePosition::new(1, 18), SourcePosition::new(1, 23))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                BoldEnd,
+                SourceSpan::new(SourcePosition::new(1, 23), SourcePosition::new(1, 25))
+            )
+        );
+        assert!(tokenizer.next().is_none());
+    }
+
+    #[test]
+    fn tokenize_strikethrough_and_ignore_tildes_in_code() {
+        let src = "~~a `~~` b~~";
+
+        let tokenizer = Tokenizer::new(
+            src.to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 13)),
+        );
+
+        assert_eq!(
+            tokenizer
+                .map(|token| token.kind().clone())
+                .collect::<Vec<_>>(),
+            vec![
+                StrikethroughStart,
+                Text("a ".to_string()),
+                CodeStart,
+                Text("~~".to_string()),
+                CodeEnd,
+                Text(" b".to_string()),
+                StrikethroughEnd,
+            ]
+        );
+    }
+
     #[test]
     fn tokenize_bold_and_italic() {
         let src = "***THIS IS TEXT***";
@@ -1779,6 +2419,7 @@ links like [here](https://example.com).";
                 Link {
                     label: "here".to_string(),
                     target: "https://example.com".to_string(),
+                    title: None,
                 },
                 SourceSpan::new(SourcePosition::new(2, 12), SourcePosition::new(2, 39))
             )
@@ -1815,6 +2456,7 @@ links like [here](https://example.com).";
                 Image {
                     label: "alt text".to_string(),
                     src: "https://example.com/image.png".to_string(),
+                    title: None,
                 },
                 SourceSpan::new(SourcePosition::new(1, 25), SourcePosition::new(1, 67))
             )
@@ -1876,4 +2518,341 @@ a function like #Image(
         );
         assert!(tokenizer.next().is_none());
     }
+
+    #[test]
+    fn tokenize_footnote_reference() {
+        let src = "Some claim[^1] and [^not a footnote].";
+
+        let mut tokenizer = Tokenizer::new(
+            src.to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 38)),
+        );
+
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                Text("Some claim".to_string()),
+                SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 11))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                FootnoteReference {
+                    label: "1".to_string()
+                },
+                SourceSpan::new(SourcePosition::new(1, 11), SourcePosition::new(1, 15))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                Text(" and [^not a footnote].".to_string()),
+                SourceSpan::new(SourcePosition::new(1, 15), SourcePosition::new(1, 38))
+            )
+        );
+        assert!(tokenizer.next().is_none());
+    }
+
+    #[test]
+    fn tokenize_link_reference() {
+        let src = "See [the docs][docs], [Docs][] and [this](x.html) but not [that] [one].";
+
+        let mut tokenizer = Tokenizer::new(
+            src.to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 72)),
+        );
+
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                Text("See ".to_string()),
+                SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 5))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                LinkReference {
+                    label: "the docs".to_string(),
+                    reference: "docs".to_string(),
+                    src: "[the docs][docs]".to_string(),
+                },
+                SourceSpan::new(SourcePosition::new(1, 5), SourcePosition::new(1, 21))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                Text(", ".to_string()),
+                SourceSpan::new(SourcePosition::new(1, 21), SourcePosition::new(1, 23))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                LinkReference {
+                    label: "Docs".to_string(),
+                    reference: "Docs".to_string(),
+                    src: "[Docs][]".to_string(),
+                },
+                SourceSpan::new(SourcePosition::new(1, 23), SourcePosition::new(1, 31))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                Text(" and ".to_string()),
+                SourceSpan::new(SourcePosition::new(1, 31), SourcePosition::new(1, 36))
+            )
+        );
+        assert!(matches!(tokenizer.next().unwrap().kind(), Link { .. }));
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                Text(" but not [that] [one].".to_string()),
+                SourceSpan::new(SourcePosition::new(1, 50), SourcePosition::new(1, 72))
+            )
+        );
+        assert!(tokenizer.next().is_none());
+    }
+
+    #[test]
+    fn tokenize_image_reference() {
+        let src = "A cat: ![Cat][cat-photo]";
+
+        let mut tokenizer = Tokenizer::new(
+            src.to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 25)),
+        );
+
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                Text("A cat: ".to_string()),
+                SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 8))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                ImageReference {
+                    label: "Cat".to_string(),
+                    reference: "cat-photo".to_string(),
+                    src: "![Cat][cat-photo]".to_string(),
+                },
+                SourceSpan::new(SourcePosition::new(1, 8), SourcePosition::new(1, 25))
+            )
+        );
+        assert!(tokenizer.next().is_none());
+    }
+
+    #[test]
+    fn tokenize_link_and_image_titles() {
+        let src = "[here](https://example.com \"Example\") ![cat](cat.png 'A cat')";
+
+        let mut tokenizer = Tokenizer::new(
+            src.to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 63)),
+        );
+
+        assert_eq!(
+            tokenizer.next().unwrap().kind(),
+            &Link {
+                label: "here".to_string(),
+                target: "https://example.com".to_string(),
+                title: Some("Example".to_string()),
+            }
+        );
+        assert_eq!(tokenizer.next().unwrap().kind(), &Text(" ".to_string()));
+        assert_eq!(
+            tokenizer.next().unwrap().kind(),
+            &Image {
+                label: "cat".to_string(),
+                src: "cat.png".to_string(),
+                title: Some("A cat".to_string()),
+            }
+        );
+        assert!(tokenizer.next().is_none());
+    }
+
+    #[test]
+    fn tokenize_autolinks() {
+        let src = "See <https://example.com/docs> or mail <team@example.com>, not <br>.";
+
+        let tokens = Tokenizer::new(
+            src.to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 69)),
+        )
+        .map(|token| token.kind().clone())
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Text("See ".to_string()),
+                Link {
+                    label: "https://example.com/docs".to_string(),
+                    target: "https://example.com/docs".to_string(),
+                    title: None,
+                },
+                Text(" or mail ".to_string()),
+                Link {
+                    label: "team@example.com".to_string(),
+                    target: "mailto:team@example.com".to_string(),
+                    title: None,
+                },
+                Text(", not <br>.".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_bare_links() {
+        let src = "Visit https://example.com/a_(b). (See www.example.com/x?y=1) or write to me.team@example.org.";
+
+        let tokens = Tokenizer::new(
+            src.to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 95)),
+        )
+        .map(|token| token.kind().clone())
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Text("Visit ".to_string()),
+                Link {
+                    label: "https://example.com/a_(b)".to_string(),
+                    target: "https://example.com/a_(b)".to_string(),
+                    title: None,
+                },
+                Text(". (See ".to_string()),
+                Link {
+                    label: "www.example.com/x?y=1".to_string(),
+                    target: "http://www.example.com/x?y=1".to_string(),
+                    title: None,
+                },
+                Text(") or write to ".to_string()),
+                Link {
+                    label: "me.team@example.org".to_string(),
+                    target: "mailto:me.team@example.org".to_string(),
+                    title: None,
+                },
+                Text(".".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_text_resembling_bare_links_as_text() {
+        let src = "Use http:// or xwww.example.com or a@b and e.g. this.";
+
+        let tokens = Tokenizer::new(
+            src.to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 54)),
+        )
+        .map(|token| token.kind().clone())
+        .collect::<Vec<_>>();
+
+        assert_eq!(tokens, vec![Text(src.to_string())]);
+    }
+
+    #[test]
+    fn tokenize_backslash_escapes() {
+        let src = r"\# \[not a link\](x) \`code\` \\ \a \~~no strike~~";
+
+        let tokens = Tokenizer::new(
+            src.to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 49)),
+        )
+        .map(|token| token.kind().clone())
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            tokens,
+            vec![Text(
+                r"# [not a link](x) `code` \ \a ~~no strike~~".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn tokenize_entities() {
+        let src =
+            "Tom &amp; Jerry&nbsp;&#x2014; &copy;&#169; &unknown; & more, but `&amp;` in code";
+
+        let tokens = Tokenizer::new(
+            src.to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 82)),
+        )
+        .map(|token| token.kind().clone())
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Text("Tom & Jerry\u{a0}— ©© &unknown; & more, but ".to_string()),
+                CodeStart,
+                Text("&amp;".to_string()),
+                CodeEnd,
+                Text(" in code".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_escapes_in_link() {
+        let src = r"[a \] b &amp; c](https://example.com/\(x\) 'It\'s')";
+
+        let mut tokenizer = Tokenizer::new(
+            src.to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 49)),
+        );
+
+        assert_eq!(
+            tokenizer.next().unwrap().kind(),
+            &Link {
+                label: "a ] b & c".to_string(),
+                target: "https://example.com/(x)".to_string(),
+                title: Some("It's".to_string()),
+            }
+        );
+        assert!(tokenizer.next().is_none());
+    }
+
+    #[test]
+    fn tokenize_hard_line_breaks() {
+        let src = "First  \n  second\\\nthird \nfourth  ";
+
+        let mut tokenizer = Tokenizer::new(
+            src.to_string(),
+            SourceSpan::new(SourcePosition::zero(), SourcePosition::new(4, 9)),
+        );
+
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                Text("First".to_string()),
+                SourceSpan::new(SourcePosition::zero(), SourcePosition::new(1, 8))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap(),
+            Token::new(
+                LineBreak,
+                SourceSpan::new(SourcePosition::new(1, 8), SourcePosition::new(2, 3))
+            )
+        );
+        assert_eq!(
+            tokenizer.next().unwrap().kind(),
+            &Text("second".to_string())
+        );
+        assert_eq!(tokenizer.next().unwrap().kind(), &LineBreak);
+        assert_eq!(
+            tokenizer.next().unwrap().kind(),
+            &Text("third fourth  ".to_string())
+        );
+        assert!(tokenizer.next().is_none());
+    }
 }
@@ -0,0 +1,209 @@
+//! Best-effort results that carry the diagnostics found along the way.
+
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::warning::WarningCode;
+
+/// A value produced despite problems in the source, together with the diagnostics describing them.
+#[derive(Debug)]
+pub struct Recovered<T> {
+    value: T,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<T> Recovered<T> {
+    pub fn new(value: T, diagnostics: Vec<Diagnostic>) -> Self {
+        Self { value, diagnostics }
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Whether any of the diagnostics is an error, meaning the value is only a best-effort result.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity() == Severity::Error)
+    }
+
+    /// Promote all warnings to errors.
+    pub fn deny_all_warnings(self) -> Self {
+        self.deny_warnings_matching(|_| true)
+    }
+
+    /// Promote the warnings with the given codes to errors.
+    pub fn deny_warnings(self, codes: &[WarningCode]) -> Self {
+        self.deny_warnings_matching(|diagnostic| {
+            codes
+                .iter()
+                .any(|code| diagnostic.code() == Some(code.name()))
+        })
+    }
+
+    fn deny_warnings_matching(mut self, predicate: impl Fn(&Diagnostic) -> bool) -> Self {
+        self.diagnostics = self
+            .diagnostics
+            .into_iter()
+            .map(|diagnostic| {
+                if diagnostic.severity() == Severity::Warning && predicate(&diagnostic) {
+                    diagnostic.into_error()
+                } else {
+                    diagnostic
+                }
+            })
+            .collect();
+
+        self
+    }
+
+    pub fn into_parts(self) -> (T, Vec<Diagnostic>) {
+        (self.value, self.diagnostics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parse_with_recovery, render, Format, Severity, SourcePosition, SourceSpan, WarningCode,
+    };
+
+    #[test]
+    fn should_collect_all_parse_errors() {
+        let src = "\
+> A quote
+without marker
+
+Some text
+
+```js
+console.log('Hello');";
+
+        let recovered = parse_with_recovery(Box::new(src.as_bytes())).unwrap();
+
+        assert!(recovered.has_errors());
+        assert_eq!(recovered.diagnostics().len(), 2);
+        assert_eq!(
+            recovered.diagnostics()[0].message(),
+            "Found no quote line start character '>' in line 2 (in quote block)"
+        );
+        assert_eq!(
+            recovered.diagnostics()[1].message(),
+            "Code block must be ended with '```' (in code block)"
+        );
+    }
+
+    #[test]
+    fn should_render_failed_blocks_as_text() {
+        let src = "\
+Some text
+
+```js
+console.log('Hello');";
+
+        let (document, diagnostics) = parse_with_recovery(Box::new(src.as_bytes()))
+            .unwrap()
+            .into_parts();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            render(&document, Format::LetterScript),
+            "\
+<paragraph>Some text</paragraph>
+<paragraph>```js
+console.log('Hello');</paragraph>
+"
+        );
+    }
+
+    #[test]
+    fn should_not_report_diagnostics_for_valid_source() {
+        let src = "# Title\n\nSome text";
+
+        let recovered = parse_with_recovery(Box::new(src.as_bytes())).unwrap();
+
+        assert!(!recovered.has_errors());
+        assert!(recovered.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn should_report_warnings_after_errors() {
+        let src = "\
+# Title
+
+### Subtitle
+
+![](cat.png)
+
+> A quote
+without marker";
+
+        let recovered = parse_with_recovery(Box::new(src.as_bytes())).unwrap();
+
+        let diagnostics = recovered
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| (diagnostic.severity(), diagnostic.code()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            vec![
+                (Severity::Error, None),
+                (Severity::Warning, Some("heading-level-jump")),
+                (Severity::Warning, Some("empty-image-alt")),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_deny_warnings() {
+        let src = "\
+# Title
+
+### Subtitle
+
+![](cat.png)";
+
+        let recovered = parse_with_recovery(Box::new(src.as_bytes())).unwrap();
+        assert!(!recovered.has_errors());
+
+        let recovered = recovered.deny_warnings(&[WarningCode::EmptyImageAlt]);
+        let severities = recovered
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| diagnostic.severity())
+            .collect::<Vec<_>>();
+        assert_eq!(severities, vec![Severity::Warning, Severity::Error]);
+
+        let recovered = recovered.deny_all_warnings();
+        assert!(recovered
+            .diagnostics()
+            .iter()
+            .all(|diagnostic| diagnostic.severity() == Severity::Error));
+    }
+
+    #[test]
+    fn should_point_to_malformed_table_delimiter_row() {
+        let src = "\
+| A | B |
+| --- | -x- |";
+
+        let recovered = parse_with_recovery(Box::new(src.as_bytes())).unwrap();
+
+        assert_eq!(recovered.diagnostics().len(), 1);
+        assert_eq!(
+            recovered.diagnostics()[0].message(),
+            "Invalid table delimiter cell '-x-' (in table block)"
+        );
+        assert_eq!(
+            recovered.diagnostics()[0].span(),
+            Some(&SourceSpan::new(
+                SourcePosition::new(2, 1),
+                SourcePosition::new(2, 14)
+            ))
+        );
+    }
+}
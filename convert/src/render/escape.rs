@@ -0,0 +1,228 @@
+/// Escape the given text content so that it cannot be mistaken for markup.
+pub(crate) fn escape_text(src: &str) -> String {
+    let mut result = String::with_capacity(src.len());
+
+    for c in src.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Escape the given value to be used in between double quotes of an attribute.
+pub(crate) fn escape_attribute_value(src: &str) -> String {
+    let mut result = String::with_capacity(src.len());
+
+    for c in src.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Check whether the given name is a valid element or attribute name in markup like `note` or `data-x`.
+pub(crate) fn is_markup_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) if is_markup_name_start(first) => chars.all(is_markup_name_char),
+        _ => false,
+    }
+}
+
+/// Turn the given name into a valid markup name by replacing invalid characters with `-`.
+/// Names not starting with a letter or `_` are prefixed with `_`.
+pub(crate) fn to_markup_name(name: &str) -> String {
+    let mut result = String::with_capacity(name.len() + 1);
+    if !name.chars().next().is_some_and(is_markup_name_start) {
+        result.push('_');
+    }
+
+    for c in name.chars() {
+        if is_markup_name_char(c) {
+            result.push(c);
+        } else {
+            result.push('-');
+        }
+    }
+
+    result
+}
+
+fn is_markup_name_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_markup_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | '.')
+}
+
+/// Escape the given text so that LaTeX special characters are typeset literally.
+pub(crate) fn escape_latex(src: &str) -> String {
+    let mut result = String::with_capacity(src.len());
+
+    for c in src.chars() {
+        match c {
+            '\\' => result.push_str("\\textbackslash{}"),
+            '{' | '}' | '&' | '%' | '$' | '#' | '_' => {
+                result.push('\\');
+                result.push(c);
+            }
+            '~' => result.push_str("\\textasciitilde{}"),
+            '^' => result.push_str("\\textasciicircum{}"),
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Escape the given URL to be used as argument of the LaTeX `\href` or `\url` commands.
+pub(crate) fn escape_latex_url(src: &str) -> String {
+    let mut result = String::with_capacity(src.len());
+
+    for c in src.chars() {
+        match c {
+            '\\' | '{' | '}' | '%' | '#' => {
+                result.push('\\');
+                result.push(c);
+            }
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Escape the given text so that it cannot be mistaken for Typst markup.
+pub(crate) fn escape_typst(src: &str) -> String {
+    let mut result = String::with_capacity(src.len());
+
+    for c in src.chars() {
+        if matches!(
+            c,
+            '\\' | '*'
+                | '_'
+                | '#'
+                | '$'
+                | '@'
+                | '<'
+                | '>'
+                | '['
+                | ']'
+                | '`'
+                | '~'
+                | '/'
+                | '='
+                | '-'
+                | '+'
+        ) {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+
+    result
+}
+
+/// Escape the given value to be used in between double quotes of a Typst string literal.
+pub(crate) fn escape_typst_string(src: &str) -> String {
+    let mut result = String::with_capacity(src.len());
+
+    for c in src.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '"' => result.push_str("\\\""),
+            '\n' => result.push_str("\\n"),
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_escape_text() {
+        assert_eq!(
+            escape_text("Tom & Jerry <3 \"cheese\""),
+            "Tom &amp; Jerry &lt;3 \"cheese\""
+        );
+    }
+
+    #[test]
+    fn should_escape_attribute_value() {
+        assert_eq!(
+            escape_attribute_value("https://example.com/?a=1&b=\"<2>\""),
+            "https://example.com/?a=1&amp;b=&quot;&lt;2&gt;&quot;"
+        );
+    }
+
+    #[test]
+    fn should_check_markup_names() {
+        assert!(is_markup_name("note"));
+        assert!(is_markup_name("data-x_1.2"));
+        assert!(is_markup_name("_private"));
+        assert!(!is_markup_name(""));
+        assert!(!is_markup_name("x y"));
+        assert!(!is_markup_name("\"x\""));
+        assert!(!is_markup_name("1st"));
+        assert!(!is_markup_name("-x"));
+    }
+
+    #[test]
+    fn should_mangle_invalid_markup_names() {
+        assert_eq!(to_markup_name("note"), "note");
+        assert_eq!(to_markup_name("my note"), "my-note");
+        assert_eq!(to_markup_name("1st"), "_1st");
+        assert_eq!(to_markup_name(""), "_");
+    }
+
+    #[test]
+    fn should_keep_text_without_special_chars() {
+        assert_eq!(escape_text("Hello World!"), "Hello World!");
+        assert_eq!(escape_attribute_value("image.png"), "image.png");
+    }
+
+    #[test]
+    fn should_escape_latex() {
+        assert_eq!(
+            escape_latex("50% of $10 & #1_a {x} ~ ^ \\"),
+            "50\\% of \\$10 \\& \\#1\\_a \\{x\\} \\textasciitilde{} \\textasciicircum{} \\textbackslash{}"
+        );
+    }
+
+    #[test]
+    fn should_escape_latex_url() {
+        assert_eq!(
+            escape_latex_url("https://example.com/a_b?c=50%#top"),
+            "https://example.com/a_b?c=50\\%\\#top"
+        );
+    }
+
+    #[test]
+    fn should_escape_typst() {
+        assert_eq!(
+            escape_typst("A *star* costs $5 #1 // [x] - <y>"),
+            "A \\*star\\* costs \\$5 \\#1 \\/\\/ \\[x\\] \\- \\<y\\>"
+        );
+    }
+
+    #[test]
+    fn should_escape_typst_string() {
+        assert_eq!(escape_typst_string("say \"hi\"\\n"), "say \\\"hi\\\"\\\\n");
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Output format of the converted document.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Format {
+    /// The Letter script markup.
+    #[default]
+    LetterScript,
+
+    /// A standalone HTML document, for example to preview it in a browser.
+    Html,
+
+    /// A standalone LaTeX document.
+    Latex,
+
+    /// Typst markup.
+    Typst,
+
+    /// The document tree as JSON, including the source spans of all nodes.
+    Json,
+}
+
+impl Format {
+    /// All supported formats.
+    pub const ALL: [Format; 5] = [
+        Format::LetterScript,
+        Format::Html,
+        Format::Latex,
+        Format::Typst,
+        Format::Json,
+    ];
+
+    /// Name of the format as used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::LetterScript => "letter-script",
+            Format::Html => "html",
+            Format::Latex => "latex",
+            Format::Typst => "typst",
+            Format::Json => "json",
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Format::ALL
+            .iter()
+            .find(|format| format.name() == s)
+            .copied()
+            .ok_or_else(|| format!("Unknown format '{}'", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_format_by_name() {
+        for format in Format::ALL {
+            assert_eq!(format.name().parse::<Format>(), Ok(format));
+        }
+    }
+
+    #[test]
+    fn should_reject_unknown_format() {
+        assert_eq!(
+            "pdf".parse::<Format>(),
+            Err("Unknown format 'pdf'".to_string())
+        );
+    }
+}
@@ -0,0 +1,786 @@
+use crate::render::escape::is_markup_name;
+use crate::render::markup::{Display, EmptyElementStyle, MarkupElement, MarkupNode, MarkupPrinter};
+use crate::render::options::RenderOptions;
+use crate::render::text::{is_running_text, plain_text};
+use crate::transformer::{LetterScriptNodeId, LetterScriptNodeKind, LetterScriptTree};
+
+const DOCTYPE: &str = "<!DOCTYPE html>";
+const MAX_HEADING_LEVEL: usize = 6;
+
+/// Render the Letter document as standalone HTML document to be previewed in a browser.
+pub(crate) struct HtmlRenderer<'a> {
+    tree: &'a LetterScriptTree,
+    options: &'a RenderOptions,
+
+    /// Footnotes by label in the order they are first referenced, which determines their number.
+    footnotes: Vec<(&'a str, LetterScriptNodeId)>,
+}
+
+/// Where in the document a node is located.
+#[derive(Debug, Copy, Clone)]
+struct Context {
+    /// Number of sections surrounding the node.
+    section_depth: usize,
+
+    /// Whether the node is part of running text (for example in a paragraph).
+    in_text: bool,
+
+    /// Whether the node is located in the header row of a table.
+    in_table_header: bool,
+}
+
+impl<'a> HtmlRenderer<'a> {
+    pub(crate) fn new(tree: &'a LetterScriptTree, options: &'a RenderOptions) -> Self {
+        let mut footnotes = Vec::new();
+        collect_footnotes(tree, tree.root_id(), &mut footnotes);
+
+        Self {
+            tree,
+            options,
+            footnotes,
+        }
+    }
+
+    pub(crate) fn render(&self) -> String {
+        let head =
+            MarkupElement::new("head", Display::Block).with_children(vec![MarkupElement::empty(
+                "meta",
+                Display::Block,
+            )
+            .with_attribute("charset", "utf-8")
+            .into()]);
+        let mut body =
+            MarkupElement::new("body", Display::Block).with_children(self.to_markup_nodes(
+                self.tree.root_id(),
+                Context {
+                    section_depth: 0,
+                    in_text: false,
+                    in_table_header: false,
+                },
+            ));
+        if !self.footnotes.is_empty() {
+            body.push_child(self.to_footnote_section().into());
+        }
+        let html = MarkupElement::new("html", Display::Block)
+            .with_children(vec![head.into(), body.into()]);
+
+        let printer = MarkupPrinter::new(self.options, EmptyElementStyle::Void);
+        let document = printer.print(&[html.into()]);
+        if document.ends_with('\n') {
+            format!("{}\n{}", DOCTYPE, document)
+        } else {
+            format!("{}{}", DOCTYPE, document)
+        }
+    }
+
+    fn to_markup_nodes(&self, node_id: LetterScriptNodeId, context: Context) -> Vec<MarkupNode> {
+        let node = self.tree.get_node(node_id);
+        node.children()
+            .iter()
+            .map(|child_id| {
+                let in_text = context.in_text
+                    || is_running_text(node.kind(), self.tree.get_node(*child_id).kind());
+                self.to_markup_node(*child_id, Context { in_text, ..context })
+            })
+            .collect()
+    }
+
+    fn to_markup_node(&self, node_id: LetterScriptNodeId, context: Context) -> MarkupNode {
+        let text_context = Context {
+            in_text: true,
+            ..context
+        };
+
+        let element = match self.tree.get_node(node_id).kind() {
+            LetterScriptNodeKind::Text(text) => return MarkupNode::Text(text.clone()),
+            LetterScriptNodeKind::Root => MarkupElement::new("div", Display::Block)
+                .with_children(self.to_markup_nodes(node_id, context)),
+            LetterScriptNodeKind::Section => MarkupElement::new("section", Display::Block)
+                .with_children(self.to_markup_nodes(
+                    node_id,
+                    Context {
+                        section_depth: context.section_depth + 1,
+                        ..context
+                    },
+                )),
+            LetterScriptNodeKind::Heading => {
+                let level = (context.section_depth + 1).min(MAX_HEADING_LEVEL);
+                MarkupElement::new(format!("h{}", level), Display::TextBlock)
+                    .with_children(self.to_markup_nodes(node_id, text_context))
+            }
+            LetterScriptNodeKind::Paragraph => MarkupElement::new("p", Display::TextBlock)
+                .with_children(self.to_markup_nodes(node_id, text_context)),
+            LetterScriptNodeKind::Image { src, title } => {
+                let mut image = MarkupElement::empty("img", Display::Inline)
+                    .with_attribute("src", src)
+                    .with_attribute("alt", plain_text(self.tree, node_id));
+                if let Some(title) = title {
+                    image = image.with_attribute("title", title);
+                }
+
+                if context.in_text {
+                    image
+                } else {
+                    MarkupElement::new("figure", Display::TextBlock)
+                        .with_children(vec![image.into()])
+                }
+            }
+            LetterScriptNodeKind::Quote => MarkupElement::new("blockquote", Display::MixedBlock)
+                .with_children(self.to_markup_nodes(node_id, context)),
+            LetterScriptNodeKind::List { ordered } => {
+                let name = if *ordered { "ol" } else { "ul" };
+                MarkupElement::new(name, Display::Block)
+                    .with_children(self.to_list_items(node_id, context))
+            }
+            LetterScriptNodeKind::ListItem { checked } => {
+                let mut item = MarkupElement::new("li", Display::TextBlock);
+                if let Some(checked) = checked {
+                    let mut checkbox = MarkupElement::empty("input", Display::Inline)
+                        .with_attribute("type", "checkbox")
+                        .with_attribute("disabled", "");
+                    if *checked {
+                        checkbox = checkbox.with_attribute("checked", "");
+                    }
+                    item.push_child(checkbox.into());
+                }
+
+                item.with_children(self.to_markup_nodes(node_id, text_context))
+            }
+            LetterScriptNodeKind::HorizontalRule => MarkupElement::empty("hr", Display::Block),
+            LetterScriptNodeKind::LineBreak => MarkupElement::empty("br", Display::Inline),
+            LetterScriptNodeKind::Link { target, title } => {
+                let mut link =
+                    MarkupElement::new("a", Display::Inline).with_attribute("href", target);
+                if let Some(title) = title {
+                    link = link.with_attribute("title", title);
+                }
+
+                link.with_children(self.to_markup_nodes(node_id, text_context))
+            }
+            LetterScriptNodeKind::Bold => MarkupElement::new("strong", Display::Inline)
+                .with_children(self.to_markup_nodes(node_id, text_context)),
+            LetterScriptNodeKind::Italic => MarkupElement::new("em", Display::Inline)
+                .with_children(self.to_markup_nodes(node_id, text_context)),
+            LetterScriptNodeKind::Strikethrough => MarkupElement::new("del", Display::Inline)
+                .with_children(self.to_markup_nodes(node_id, text_context)),
+            LetterScriptNodeKind::Footnote { label } => {
+                let number = self
+                    .footnotes
+                    .iter()
+                    .position(|(footnote_label, _)| footnote_label == label)
+                    .unwrap()
+                    + 1;
+                let link = MarkupElement::new("a", Display::Inline)
+                    .with_attribute("href", format!("#fn-{}", label))
+                    .with_children(vec![MarkupNode::Text(number.to_string())]);
+
+                MarkupElement::new("sup", Display::Inline).with_children(vec![link.into()])
+            }
+            LetterScriptNodeKind::Code {
+                language,
+                attributes,
+            } => {
+                let mut code = MarkupElement::new("code", Display::Inline);
+                if let Some(language) = language {
+                    code = code.with_attribute("class", format!("language-{}", language));
+                }
+                let code = code.with_children(self.to_markup_nodes(node_id, text_context));
+
+                if context.in_text {
+                    code
+                } else {
+                    // Attributes like the title are kept for scripts and styles to pick up
+                    let mut attributes = attributes.iter().collect::<Vec<_>>();
+                    attributes.sort();
+
+                    attributes.into_iter().fold(
+                        MarkupElement::new("pre", Display::TextBlock)
+                            .with_children(vec![code.into()]),
+                        |element, (key, value)| {
+                            element.with_attribute(format!("data-{}", key), value)
+                        },
+                    )
+                }
+            }
+            LetterScriptNodeKind::Table => self.to_table(node_id, context),
+            LetterScriptNodeKind::TableHeaderRow => MarkupElement::new("tr", Display::Block)
+                .with_children(self.to_markup_nodes(
+                    node_id,
+                    Context {
+                        in_table_header: true,
+                        ..context
+                    },
+                )),
+            LetterScriptNodeKind::TableRow => MarkupElement::new("tr", Display::Block)
+                .with_children(self.to_markup_nodes(node_id, context)),
+            LetterScriptNodeKind::TableCell { alignment } => {
+                let name = if context.in_table_header { "th" } else { "td" };
+                let element = MarkupElement::new(name, Display::TextBlock)
+                    .with_children(self.to_markup_nodes(node_id, text_context));
+
+                match alignment {
+                    Some(alignment) => {
+                        element.with_attribute("style", format!("text-align: {}", alignment.name()))
+                    }
+                    None => element,
+                }
+            }
+            LetterScriptNodeKind::Function { name, parameters } => {
+                // There is no HTML equivalent for Letter functions, thus we keep them as data attributes
+                let (element_name, display) = if context.in_text {
+                    ("span", Display::Inline)
+                } else {
+                    ("div", Display::TextBlock)
+                };
+
+                let mut parameters = parameters
+                    .iter()
+                    .filter(|(key, _)| is_data_attribute_key(key))
+                    .collect::<Vec<_>>();
+                parameters.sort();
+
+                parameters.into_iter().fold(
+                    MarkupElement::new(element_name, display).with_attribute("data-function", name),
+                    |element, (key, value)| element.with_attribute(format!("data-{}", key), value),
+                )
+            }
+        };
+
+        element.into()
+    }
+
+    /// Nested lists are placed inside the preceding list item as HTML requires.
+    fn to_list_items(&self, list_node_id: LetterScriptNodeId, context: Context) -> Vec<MarkupNode> {
+        let mut items: Vec<MarkupNode> = Vec::new();
+
+        for child_id in self.tree.get_node(list_node_id).children() {
+            let child = self.to_markup_node(*child_id, context);
+            let is_nested_list = matches!(
+                self.tree.get_node(*child_id).kind(),
+                LetterScriptNodeKind::List { .. }
+            );
+
+            match items.last_mut() {
+                Some(MarkupNode::Element(item)) if is_nested_list => item.push_child(child),
+                _ => items.push(child),
+            }
+        }
+
+        items
+    }
+
+    fn to_table(&self, table_node_id: LetterScriptNodeId, context: Context) -> MarkupElement {
+        let mut head = MarkupElement::new("thead", Display::Block);
+        let mut body = MarkupElement::new("tbody", Display::Block);
+        let mut has_head = false;
+        let mut has_body = false;
+
+        for row_id in self.tree.get_node(table_node_id).children() {
+            let row = self.to_markup_node(*row_id, context);
+            if let LetterScriptNodeKind::TableHeaderRow = self.tree.get_node(*row_id).kind() {
+                head.push_child(row);
+                has_head = true;
+            } else {
+                body.push_child(row);
+                has_body = true;
+            }
+        }
+
+        let mut table = MarkupElement::new("table", Display::Block);
+        if has_head {
+            table.push_child(head.into());
+        }
+        if has_body {
+            table.push_child(body.into());
+        }
+
+        table
+    }
+
+    /// List the footnote contents at the end of the document, numbered like their references.
+    fn to_footnote_section(&self) -> MarkupElement {
+        let context = Context {
+            section_depth: 0,
+            in_text: true,
+            in_table_header: false,
+        };
+        let items = self
+            .footnotes
+            .iter()
+            .map(|(label, node_id)| {
+                MarkupElement::new("li", Display::TextBlock)
+                    .with_attribute("id", format!("fn-{}", label))
+                    .with_children(self.to_markup_nodes(*node_id, context))
+                    .into()
+            })
+            .collect();
+
+        MarkupElement::new("section", Display::Block)
+            .with_attribute("class", "footnotes")
+            .with_children(vec![MarkupElement::new("ol", Display::Block)
+                .with_children(items)
+                .into()])
+    }
+}
+
+/// Collect the first footnote node for every label in document order.
+fn collect_footnotes<'a>(
+    tree: &'a LetterScriptTree,
+    node_id: LetterScriptNodeId,
+    footnotes: &mut Vec<(&'a str, LetterScriptNodeId)>,
+) {
+    let node = tree.get_node(node_id);
+    if let LetterScriptNodeKind::Footnote { label } = node.kind() {
+        if !footnotes
+            .iter()
+            .any(|(footnote_label, _)| footnote_label == label)
+        {
+            footnotes.push((label, node_id));
+        }
+    }
+
+    for child_id in node.children() {
+        collect_footnotes(tree, *child_id, footnotes);
+    }
+}
+
+/// Whether the given function parameter key can be used as `data-` attribute.
+/// HTML forbids uppercase letters there, and `function` is taken by the function name itself.
+fn is_data_attribute_key(key: &str) -> bool {
+    is_markup_name(&format!("data-{}", key))
+        && !key.chars().any(|c| c.is_ascii_uppercase())
+        && key != "function"
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::render::test_util::{register_text, span};
+    use crate::util::Alignment;
+
+    use super::*;
+
+    /// Render the tree compactly and keep only the content of the HTML body.
+    fn render_body(tree: &LetterScriptTree) -> String {
+        let result = HtmlRenderer::new(tree, &RenderOptions::compact()).render();
+
+        result
+            .strip_prefix("<!DOCTYPE html><html><head><meta charset=\"utf-8\"></head><body>")
+            .unwrap()
+            .strip_suffix("</body></html>")
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn should_render_document() {
+        let mut tree = LetterScriptTree::new();
+        let paragraph_id =
+            tree.register_node(tree.root_id(), LetterScriptNodeKind::Paragraph, span());
+        register_text(&mut tree, paragraph_id, "Hello & welcome");
+
+        assert_eq!(
+            HtmlRenderer::new(&tree, &RenderOptions::default()).render(),
+            "\
+<!DOCTYPE html>
+<html>
+    <head>
+        <meta charset=\"utf-8\">
+    </head>
+    <body>
+        <p>Hello &amp; welcome</p>
+    </body>
+</html>
+"
+        );
+    }
+
+    #[test]
+    fn should_render_headings_by_section_depth() {
+        let mut tree = LetterScriptTree::new();
+        let heading_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Heading, span());
+        register_text(&mut tree, heading_id, "Title");
+
+        let mut parent_id = tree.root_id();
+        for level in 2..=7 {
+            parent_id = tree.register_node(parent_id, LetterScriptNodeKind::Section, span());
+            let heading_id = tree.register_node(parent_id, LetterScriptNodeKind::Heading, span());
+            register_text(&mut tree, heading_id, &format!("Level {}", level));
+        }
+
+        assert_eq!(
+            render_body(&tree),
+            "\
+<h1>Title</h1>\
+<section><h2>Level 2</h2>\
+<section><h3>Level 3</h3>\
+<section><h4>Level 4</h4>\
+<section><h5>Level 5</h5>\
+<section><h6>Level 6</h6>\
+<section><h6>Level 7</h6>\
+</section></section></section></section></section></section>"
+        );
+    }
+
+    #[test]
+    fn should_render_formatting_and_links() {
+        let mut tree = LetterScriptTree::new();
+        let paragraph_id =
+            tree.register_node(tree.root_id(), LetterScriptNodeKind::Paragraph, span());
+        let bold_id = tree.register_node(paragraph_id, LetterScriptNodeKind::Bold, span());
+        register_text(&mut tree, bold_id, "bold");
+        let italic_id = tree.register_node(paragraph_id, LetterScriptNodeKind::Italic, span());
+        register_text(&mut tree, italic_id, "italic");
+        let strikethrough_id =
+            tree.register_node(paragraph_id, LetterScriptNodeKind::Strikethrough, span());
+        register_text(&mut tree, strikethrough_id, "struck");
+        let link_id = tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Link {
+                target: "https://example.com?a=1&b=2".to_string(),
+                title: None,
+            },
+            span(),
+        );
+        register_text(&mut tree, link_id, "link");
+        let code_id = tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Code {
+                language: None,
+                attributes: HashMap::new(),
+            },
+            span(),
+        );
+        register_text(&mut tree, code_id, "x < y");
+
+        assert_eq!(
+            render_body(&tree),
+            "<p><strong>bold</strong><em>italic</em><del>struck</del><a href=\"https://example.com?a=1&amp;b=2\">link</a><code>x &lt; y</code></p>"
+        );
+    }
+
+    #[test]
+    fn should_render_footnotes_at_end_of_document() {
+        let mut tree = LetterScriptTree::new();
+        let paragraph_id =
+            tree.register_node(tree.root_id(), LetterScriptNodeKind::Paragraph, span());
+        for label in ["b", "a", "b"] {
+            register_text(&mut tree, paragraph_id, "Text");
+            let footnote_id = tree.register_node(
+                paragraph_id,
+                LetterScriptNodeKind::Footnote {
+                    label: label.to_string(),
+                },
+                span(),
+            );
+            register_text(&mut tree, footnote_id, &format!("Note {}", label));
+        }
+
+        assert_eq!(
+            render_body(&tree),
+            "\
+<p>Text<sup><a href=\"#fn-b\">1</a></sup>Text<sup><a href=\"#fn-a\">2</a></sup>Text<sup><a href=\"#fn-b\">1</a></sup></p>\
+<section class=\"footnotes\"><ol><li id=\"fn-b\">Note b</li><li id=\"fn-a\">Note a</li></ol></section>"
+        );
+    }
+
+    #[test]
+    fn should_render_code_block_with_language_class() {
+        let mut tree = LetterScriptTree::new();
+        let code_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Code {
+                language: Some("rust".to_string()),
+                attributes: HashMap::new(),
+            },
+            span(),
+        );
+        register_text(&mut tree, code_id, "fn main() {\n}");
+
+        assert_eq!(
+            render_body(&tree),
+            "<pre><code class=\"language-rust\">fn main() {\n}</code></pre>"
+        );
+    }
+
+    #[test]
+    fn should_render_code_block_attributes_as_data_attributes() {
+        let mut tree = LetterScriptTree::new();
+        let code_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Code {
+                language: Some("rust".to_string()),
+                attributes: HashMap::from([
+                    ("title".to_string(), "main.rs".to_string()),
+                    ("highlight".to_string(), "2-4".to_string()),
+                ]),
+            },
+            span(),
+        );
+        register_text(&mut tree, code_id, "fn main() {}");
+
+        assert_eq!(
+            render_body(&tree),
+            "<pre data-highlight=\"2-4\" data-title=\"main.rs\"><code class=\"language-rust\">fn main() {}</code></pre>"
+        );
+    }
+
+    #[test]
+    fn should_render_image() {
+        let mut tree = LetterScriptTree::new();
+        let image_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Image {
+                src: "image.png".to_string(),
+                title: None,
+            },
+            span(),
+        );
+        register_text(&mut tree, image_id, "An ");
+        let bold_id = tree.register_node(image_id, LetterScriptNodeKind::Bold, span());
+        register_text(&mut tree, bold_id, "image");
+
+        assert_eq!(
+            render_body(&tree),
+            "<figure><img src=\"image.png\" alt=\"An image\"></figure>"
+        );
+    }
+
+    #[test]
+    fn should_render_link_and_image_titles() {
+        let mut tree = LetterScriptTree::new();
+        let paragraph_id =
+            tree.register_node(tree.root_id(), LetterScriptNodeKind::Paragraph, span());
+        let link_id = tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Link {
+                target: "https://example.com".to_string(),
+                title: Some("The \"best\" site".to_string()),
+            },
+            span(),
+        );
+        register_text(&mut tree, link_id, "link");
+        let image_id = tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Image {
+                src: "cat.png".to_string(),
+                title: Some("A cat".to_string()),
+            },
+            span(),
+        );
+        register_text(&mut tree, image_id, "Cat");
+
+        assert_eq!(
+            render_body(&tree),
+            "<p><a href=\"https://example.com\" title=\"The &quot;best&quot; site\">link</a><img src=\"cat.png\" alt=\"Cat\" title=\"A cat\"></p>"
+        );
+    }
+
+    #[test]
+    fn should_render_line_break() {
+        let mut tree = LetterScriptTree::new();
+        let paragraph_id =
+            tree.register_node(tree.root_id(), LetterScriptNodeKind::Paragraph, span());
+        register_text(&mut tree, paragraph_id, "First");
+        tree.register_node(paragraph_id, LetterScriptNodeKind::LineBreak, span());
+        register_text(&mut tree, paragraph_id, "second");
+
+        assert_eq!(render_body(&tree), "<p>First<br>second</p>");
+    }
+
+    #[test]
+    fn should_render_nested_lists() {
+        let mut tree = LetterScriptTree::new();
+        let list_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::List { ordered: false },
+            span(),
+        );
+        let item_id = tree.register_node(
+            list_id,
+            LetterScriptNodeKind::ListItem { checked: None },
+            span(),
+        );
+        register_text(&mut tree, item_id, "A");
+        let nested_list_id = tree.register_node(
+            list_id,
+            LetterScriptNodeKind::List { ordered: true },
+            span(),
+        );
+        let nested_item_id = tree.register_node(
+            nested_list_id,
+            LetterScriptNodeKind::ListItem { checked: None },
+            span(),
+        );
+        register_text(&mut tree, nested_item_id, "B");
+        let item_id = tree.register_node(
+            list_id,
+            LetterScriptNodeKind::ListItem { checked: None },
+            span(),
+        );
+        register_text(&mut tree, item_id, "C");
+
+        assert_eq!(
+            render_body(&tree),
+            "<ul><li>A<ol><li>B</li></ol></li><li>C</li></ul>"
+        );
+    }
+
+    #[test]
+    fn should_render_task_list_items_with_checkbox() {
+        let mut tree = LetterScriptTree::new();
+        let list_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::List { ordered: false },
+            span(),
+        );
+        for checked in [Some(true), Some(false), None] {
+            let item_id =
+                tree.register_node(list_id, LetterScriptNodeKind::ListItem { checked }, span());
+            register_text(&mut tree, item_id, "Task");
+        }
+
+        assert_eq!(
+            render_body(&tree),
+            "\
+<ul>\
+<li><input type=\"checkbox\" disabled=\"\" checked=\"\">Task</li>\
+<li><input type=\"checkbox\" disabled=\"\">Task</li>\
+<li>Task</li>\
+</ul>"
+        );
+    }
+
+    #[test]
+    fn should_render_table_with_head_and_body() {
+        let mut tree = LetterScriptTree::new();
+        let table_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Table, span());
+        let header_row_id =
+            tree.register_node(table_id, LetterScriptNodeKind::TableHeaderRow, span());
+        let cell_id = tree.register_node(
+            header_row_id,
+            LetterScriptNodeKind::TableCell { alignment: None },
+            span(),
+        );
+        register_text(&mut tree, cell_id, "Name");
+        let row_id = tree.register_node(table_id, LetterScriptNodeKind::TableRow, span());
+        let cell_id = tree.register_node(
+            row_id,
+            LetterScriptNodeKind::TableCell { alignment: None },
+            span(),
+        );
+        register_text(&mut tree, cell_id, "Max");
+
+        assert_eq!(
+            render_body(&tree),
+            "<table><thead><tr><th>Name</th></tr></thead><tbody><tr><td>Max</td></tr></tbody></table>"
+        );
+    }
+
+    #[test]
+    fn should_render_table_cell_alignment() {
+        let mut tree = LetterScriptTree::new();
+        let table_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Table, span());
+        let row_id = tree.register_node(table_id, LetterScriptNodeKind::TableRow, span());
+        let cell_id = tree.register_node(
+            row_id,
+            LetterScriptNodeKind::TableCell {
+                alignment: Some(Alignment::Right),
+            },
+            span(),
+        );
+        register_text(&mut tree, cell_id, "42");
+
+        assert_eq!(
+            render_body(&tree),
+            "<table><tbody><tr><td style=\"text-align: right\">42</td></tr></tbody></table>"
+        );
+    }
+
+    #[test]
+    fn should_render_inline_code_and_image_in_quote_as_text() {
+        let mut tree = LetterScriptTree::new();
+        let quote_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Quote, span());
+        register_text(&mut tree, quote_id, "Run ");
+        let code_id = tree.register_node(
+            quote_id,
+            LetterScriptNodeKind::Code {
+                language: None,
+                attributes: HashMap::new(),
+            },
+            span(),
+        );
+        register_text(&mut tree, code_id, "make");
+        register_text(&mut tree, quote_id, " now ");
+        let image_id = tree.register_node(
+            quote_id,
+            LetterScriptNodeKind::Image {
+                src: "smile.png".to_string(),
+                title: None,
+            },
+            span(),
+        );
+        register_text(&mut tree, image_id, "Smile");
+
+        assert_eq!(
+            render_body(&tree),
+            "<blockquote>Run <code>make</code> now <img src=\"smile.png\" alt=\"Smile\"></blockquote>"
+        );
+    }
+
+    #[test]
+    fn should_render_quote_and_horizontal_rule() {
+        let mut tree = LetterScriptTree::new();
+        let quote_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Quote, span());
+        register_text(&mut tree, quote_id, "Quoted");
+        tree.register_node(tree.root_id(), LetterScriptNodeKind::HorizontalRule, span());
+
+        assert_eq!(render_body(&tree), "<blockquote>Quoted</blockquote><hr>");
+    }
+
+    #[test]
+    fn should_render_function_as_data_attributes() {
+        let mut tree = LetterScriptTree::new();
+        let mut parameters = HashMap::new();
+        parameters.insert("width".to_string(), "100".to_string());
+        tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Function {
+                name: "signature".to_string(),
+                parameters,
+            },
+            span(),
+        );
+
+        assert_eq!(
+            render_body(&tree),
+            "<div data-function=\"signature\" data-width=\"100\"></div>"
+        );
+    }
+
+    #[test]
+    fn should_skip_function_parameters_invalid_as_data_attributes() {
+        let mut tree = LetterScriptTree::new();
+        let parameters = HashMap::from([
+            ("\"x y\"".to_string(), "1".to_string()),
+            ("a b".to_string(), "c".to_string()),
+            ("Width".to_string(), "2".to_string()),
+            ("function".to_string(), "other".to_string()),
+            ("src".to_string(), "a.png".to_string()),
+        ]);
+        tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Function {
+                name: "image".to_string(),
+                parameters,
+            },
+            span(),
+        );
+
+        assert_eq!(
+            render_body(&tree),
+            "<div data-function=\"image\" data-src=\"a.png\"></div>"
+        );
+    }
+}
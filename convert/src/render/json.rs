@@ -0,0 +1,334 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+use serde_json::Serializer;
+
+use crate::render::options::{Layout, RenderOptions};
+use crate::transformer::{LetterScriptNodeId, LetterScriptNodeKind, LetterScriptTree};
+use crate::util::SourceSpan;
+
+/// Render the Letter document tree as JSON to be consumed by other tools.
+pub(crate) struct JsonRenderer<'a> {
+    tree: &'a LetterScriptTree,
+    options: &'a RenderOptions,
+}
+
+#[derive(Serialize)]
+struct JsonNode<'a> {
+    #[serde(flatten)]
+    kind: JsonNodeKind<'a>,
+    span: &'a SourceSpan,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    children: Vec<JsonNode<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+enum JsonNodeKind<'a> {
+    Root,
+    Text {
+        text: &'a str,
+    },
+    Heading,
+    Paragraph,
+    Section,
+    Image {
+        src: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<&'a str>,
+    },
+    Quote,
+    List {
+        ordered: bool,
+    },
+    ListItem {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        checked: Option<bool>,
+    },
+    HorizontalRule,
+    LineBreak,
+    Link {
+        target: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<&'a str>,
+    },
+    Bold,
+    Italic,
+    Strikethrough,
+    Footnote {
+        label: &'a str,
+    },
+    Code {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        language: Option<&'a str>,
+        #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+        attributes: BTreeMap<&'a str, &'a str>,
+    },
+    Table,
+    TableHeaderRow,
+    TableRow,
+    TableCell {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        alignment: Option<&'static str>,
+    },
+    Function {
+        name: &'a str,
+        parameters: BTreeMap<&'a str, &'a str>,
+    },
+}
+
+impl<'a> JsonRenderer<'a> {
+    pub(crate) fn new(tree: &'a LetterScriptTree, options: &'a RenderOptions) -> Self {
+        Self { tree, options }
+    }
+
+    pub(crate) fn render(&self) -> String {
+        let root = self.to_json_node(self.tree.root_id());
+
+        let mut result = Vec::new();
+        match self.options.layout() {
+            Layout::Compact => root.serialize(&mut Serializer::new(&mut result)),
+            Layout::Pretty(indent) => {
+                let indent = indent.repeat(1);
+                let formatter = PrettyFormatter::with_indent(indent.as_bytes());
+                root.serialize(&mut Serializer::with_formatter(&mut result, formatter))
+            }
+        }
+        .expect("Serializing the document tree to JSON cannot fail");
+
+        let mut result = String::from_utf8(result).expect("JSON is always valid UTF-8");
+        if let Layout::Pretty(_) = self.options.layout() {
+            result.push('\n');
+        }
+
+        result
+    }
+
+    fn to_json_node(&self, node_id: LetterScriptNodeId) -> JsonNode<'a> {
+        let node = self.tree.get_node(node_id);
+
+        JsonNode {
+            kind: to_json_node_kind(node.kind()),
+            span: node.span(),
+            children: node
+                .children()
+                .iter()
+                .map(|child_id| self.to_json_node(*child_id))
+                .collect(),
+        }
+    }
+}
+
+fn to_json_node_kind(kind: &LetterScriptNodeKind) -> JsonNodeKind<'_> {
+    match kind {
+        LetterScriptNodeKind::Root => JsonNodeKind::Root,
+        LetterScriptNodeKind::Text(text) => JsonNodeKind::Text { text },
+        LetterScriptNodeKind::Heading => JsonNodeKind::Heading,
+        LetterScriptNodeKind::Paragraph => JsonNodeKind::Paragraph,
+        LetterScriptNodeKind::Section => JsonNodeKind::Section,
+        LetterScriptNodeKind::Image { src, title } => JsonNodeKind::Image {
+            src,
+            title: title.as_deref(),
+        },
+        LetterScriptNodeKind::Quote => JsonNodeKind::Quote,
+        LetterScriptNodeKind::List { ordered } => JsonNodeKind::List { ordered: *ordered },
+        LetterScriptNodeKind::ListItem { checked } => JsonNodeKind::ListItem { checked: *checked },
+        LetterScriptNodeKind::HorizontalRule => JsonNodeKind::HorizontalRule,
+        LetterScriptNodeKind::LineBreak => JsonNodeKind::LineBreak,
+        LetterScriptNodeKind::Link { target, title } => JsonNodeKind::Link {
+            target,
+            title: title.as_deref(),
+        },
+        LetterScriptNodeKind::Bold => JsonNodeKind::Bold,
+        LetterScriptNodeKind::Italic => JsonNodeKind::Italic,
+        LetterScriptNodeKind::Strikethrough => JsonNodeKind::Strikethrough,
+        LetterScriptNodeKind::Footnote { label } => JsonNodeKind::Footnote { label },
+        LetterScriptNodeKind::Code {
+            language,
+            attributes,
+        } => JsonNodeKind::Code {
+            language: language.as_deref(),
+            attributes: attributes
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str()))
+                .collect(),
+        },
+        LetterScriptNodeKind::Table => JsonNodeKind::Table,
+        LetterScriptNodeKind::TableHeaderRow => JsonNodeKind::TableHeaderRow,
+        LetterScriptNodeKind::TableRow => JsonNodeKind::TableRow,
+        LetterScriptNodeKind::TableCell { alignment } => JsonNodeKind::TableCell {
+            alignment: alignment.map(|alignment| alignment.name()),
+        },
+        LetterScriptNodeKind::Function { name, parameters } => JsonNodeKind::Function {
+            name,
+            parameters: parameters
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str()))
+                .collect(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::render::options::Indent;
+    use crate::util::SourcePosition;
+
+    use super::*;
+
+    fn span(start: (usize, usize), end: (usize, usize)) -> SourceSpan {
+        SourceSpan::new(
+            SourcePosition::new(start.0, start.1),
+            SourcePosition::new(end.0, end.1),
+        )
+    }
+
+    #[test]
+    fn should_render_tree_with_spans() {
+        let mut tree = LetterScriptTree::new();
+        let code_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Code {
+                language: Some("js".to_string()),
+                attributes: HashMap::new(),
+            },
+            span((1, 1), (3, 4)),
+        );
+        tree.register_node(
+            code_id,
+            LetterScriptNodeKind::Text("x".to_string()),
+            span((1, 1), (3, 4)),
+        );
+
+        assert_eq!(
+            JsonRenderer::new(&tree, &RenderOptions::pretty(Indent::Spaces(2))).render(),
+            r#"{
+  "kind": "root",
+  "span": {
+    "start": {
+      "line": 1,
+      "column": 1
+    },
+    "end": {
+      "line": 1,
+      "column": 1
+    }
+  },
+  "children": [
+    {
+      "kind": "code",
+      "language": "js",
+      "span": {
+        "start": {
+          "line": 1,
+          "column": 1
+        },
+        "end": {
+          "line": 3,
+          "column": 4
+        }
+      },
+      "children": [
+        {
+          "kind": "text",
+          "text": "x",
+          "span": {
+            "start": {
+              "line": 1,
+              "column": 1
+            },
+            "end": {
+              "line": 3,
+              "column": 4
+            }
+          }
+        }
+      ]
+    }
+  ]
+}
+"#
+        );
+    }
+
+    #[test]
+    fn should_render_attributes() {
+        let mut tree = LetterScriptTree::new();
+        let paragraph_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Paragraph,
+            span((1, 1), (1, 1)),
+        );
+        tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Link {
+                target: "https://example.com".to_string(),
+                title: None,
+            },
+            span((1, 1), (1, 1)),
+        );
+        tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Image {
+                src: "image.png".to_string(),
+                title: None,
+            },
+            span((1, 1), (1, 1)),
+        );
+        let mut parameters = HashMap::new();
+        parameters.insert("width".to_string(), "100px".to_string());
+        parameters.insert("height".to_string(), "50px".to_string());
+        tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Function {
+                name: "image".to_string(),
+                parameters,
+            },
+            span((1, 1), (1, 1)),
+        );
+
+        let span_json = r#""span":{"start":{"line":1,"column":1},"end":{"line":1,"column":1}}"#;
+        assert_eq!(
+            JsonRenderer::new(&tree, &RenderOptions::compact()).render(),
+            format!(
+                r#"{{"kind":"root",{span},"children":[{{"kind":"paragraph",{span},"children":[{{"kind":"link","target":"https://example.com",{span}}},{{"kind":"image","src":"image.png",{span}}},{{"kind":"function","name":"image","parameters":{{"height":"50px","width":"100px"}},{span}}}]}}]}}"#,
+                span = span_json
+            )
+        );
+    }
+
+    #[test]
+    fn should_render_source_positions_of_parsed_nodes() {
+        let result = crate::convert_with_options(
+            Box::new("# Title\n\npara\n".as_bytes()),
+            crate::Format::Json,
+            &RenderOptions::compact(),
+        )
+        .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+        let position = |value: &serde_json::Value| {
+            (
+                value["line"].as_u64().unwrap(),
+                value["column"].as_u64().unwrap(),
+            )
+        };
+        let span_of = |node: &serde_json::Value| {
+            (
+                position(&node["span"]["start"]),
+                position(&node["span"]["end"]),
+            )
+        };
+
+        let heading = &json["children"][0];
+        let paragraph = &json["children"][1];
+        assert_eq!(span_of(&json), ((1, 1), (3, 5)));
+        assert_eq!(span_of(heading), ((1, 1), (1, 8)));
+        assert_eq!(span_of(&heading["children"][0]), ((1, 3), (1, 8)));
+        assert_eq!(span_of(paragraph), ((3, 1), (3, 5)));
+        assert_eq!(span_of(&paragraph["children"][0]), ((3, 1), (3, 5)));
+    }
+}
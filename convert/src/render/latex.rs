@@ -0,0 +1,778 @@
+use std::collections::HashMap;
+
+use crate::render::escape::{escape_latex, escape_latex_url};
+use crate::render::options::{Layout, RenderOptions};
+use crate::render::table::column_alignments;
+use crate::render::text::{is_running_text, plain_text};
+use crate::transformer::{LetterScriptNodeId, LetterScriptNodeKind, LetterScriptTree};
+use crate::util::Alignment;
+
+/// Sectioning commands by the section depth of a heading.
+const SECTION_COMMANDS: [&str; 5] = [
+    "section",
+    "subsection",
+    "subsubsection",
+    "paragraph",
+    "subparagraph",
+];
+
+/// Languages known to the listings package by the (lowercase) names code blocks commonly use for them.
+const LISTINGS_LANGUAGES: [(&[&str], &str); 24] = [
+    (&["awk"], "Awk"),
+    (&["bash"], "bash"),
+    (&["c"], "C"),
+    (&["c++", "cpp"], "C++"),
+    (&["erlang"], "erlang"),
+    (&["fortran"], "Fortran"),
+    (&["haskell", "hs"], "Haskell"),
+    (&["html"], "HTML"),
+    (&["java"], "Java"),
+    (&["lisp"], "Lisp"),
+    (&["make", "makefile"], "make"),
+    (&["matlab"], "Matlab"),
+    (&["ocaml"], "Caml"),
+    (&["pascal"], "Pascal"),
+    (&["perl"], "Perl"),
+    (&["php"], "PHP"),
+    (&["python", "py"], "Python"),
+    (&["r"], "R"),
+    (&["ruby", "rb"], "Ruby"),
+    (&["sh", "shell"], "sh"),
+    (&["sql"], "SQL"),
+    (&["tex", "latex"], "TeX"),
+    (&["vhdl"], "VHDL"),
+    (&["xml"], "XML"),
+];
+
+/// Render the Letter document as standalone LaTeX document.
+/// Nested environments (lists, tables, ...) are indented according to the render options,
+/// the compact layout omits the indentation only, as LaTeX relies on line breaks.
+pub(crate) struct LatexRenderer<'a> {
+    tree: &'a LetterScriptTree,
+    options: &'a RenderOptions,
+}
+
+impl<'a> LatexRenderer<'a> {
+    pub(crate) fn new(tree: &'a LetterScriptTree, options: &'a RenderOptions) -> Self {
+        Self { tree, options }
+    }
+
+    pub(crate) fn render(&self) -> String {
+        let body = self.render_blocks(self.tree.root_id(), 0, 0).join("\n\n");
+
+        format!(
+            "\
+\\documentclass{{article}}
+
+\\usepackage{{amssymb}}
+\\usepackage{{graphicx}}
+\\usepackage{{hyperref}}
+\\usepackage{{listings}}
+\\usepackage[normalem]{{ulem}}
+
+\\begin{{document}}
+
+{}
+
+\\end{{document}}
+",
+            body
+        )
+    }
+
+    /// Render the children of the given node as blocks.
+    /// Consecutive inline children (for example text in a quote) are combined into a single block.
+    fn render_blocks(
+        &self,
+        node_id: LetterScriptNodeId,
+        section_depth: usize,
+        level: usize,
+    ) -> Vec<String> {
+        let mut blocks = Vec::new();
+        let mut text = String::new();
+
+        let kind = self.tree.get_node(node_id).kind();
+        for child_id in self.tree.get_node(node_id).children() {
+            if is_running_text(kind, self.tree.get_node(*child_id).kind()) {
+                text.push_str(&self.render_inline(*child_id));
+                continue;
+            }
+
+            if !text.is_empty() {
+                blocks.push(format!("{}{}", self.indent(level), text));
+                text.clear();
+            }
+
+            blocks.push(self.render_block(*child_id, section_depth, level));
+        }
+
+        if !text.is_empty() {
+            blocks.push(format!("{}{}", self.indent(level), text));
+        }
+
+        blocks
+    }
+
+    fn render_block(
+        &self,
+        node_id: LetterScriptNodeId,
+        section_depth: usize,
+        level: usize,
+    ) -> String {
+        let node = self.tree.get_node(node_id);
+        let indent = self.indent(level);
+
+        match node.kind() {
+            LetterScriptNodeKind::Section => self
+                .render_blocks(node_id, section_depth + 1, level)
+                .join("\n\n"),
+            LetterScriptNodeKind::Heading => {
+                let command = SECTION_COMMANDS[section_depth.min(SECTION_COMMANDS.len() - 1)];
+                format!(
+                    "{}\\{}{{{}}}",
+                    indent,
+                    command,
+                    self.render_inline_children(node_id)
+                )
+            }
+            LetterScriptNodeKind::Image { src, .. } => {
+                let inner_indent = self.indent(level + 1);
+                let mut lines = vec![
+                    format!("{}\\begin{{figure}}[htbp]", indent),
+                    format!("{}\\centering", inner_indent),
+                    format!(
+                        "{}\\includegraphics{{{}}}",
+                        inner_indent,
+                        escape_latex_url(src)
+                    ),
+                ];
+
+                let caption = self.render_inline_children(node_id);
+                if !caption.is_empty() {
+                    lines.push(format!("{}\\caption{{{}}}", inner_indent, caption));
+                }
+
+                lines.push(format!("{}\\end{{figure}}", indent));
+                lines.join("\n")
+            }
+            LetterScriptNodeKind::Quote => self.render_environment(
+                "quote",
+                None,
+                &self
+                    .render_blocks(node_id, section_depth, level + 1)
+                    .join("\n\n"),
+                level,
+            ),
+            LetterScriptNodeKind::List { ordered } => {
+                let name = if *ordered { "enumerate" } else { "itemize" };
+                self.render_environment(
+                    name,
+                    None,
+                    &self
+                        .render_blocks(node_id, section_depth, level + 1)
+                        .join("\n"),
+                    level,
+                )
+            }
+            LetterScriptNodeKind::ListItem { checked } => {
+                let label = match checked {
+                    Some(true) => "[$\\boxtimes$]",
+                    Some(false) => "[$\\square$]",
+                    None => "",
+                };
+                format!(
+                    "{}\\item{} {}",
+                    indent,
+                    label,
+                    self.render_inline_children(node_id)
+                )
+            }
+            LetterScriptNodeKind::HorizontalRule => {
+                format!("{}\\noindent\\rule{{\\linewidth}}{{0.4pt}}", indent)
+            }
+            LetterScriptNodeKind::Code {
+                language,
+                attributes,
+            } => {
+                // Code must not be indented as that would change its content
+                let src = plain_text(self.tree, node_id);
+                let options = listing_options(language.as_deref(), attributes);
+                if options.is_empty() {
+                    format!(
+                        "{}\\begin{{verbatim}}\n{}\n{}\\end{{verbatim}}",
+                        indent, src, indent
+                    )
+                } else {
+                    format!(
+                        "{}\\begin{{lstlisting}}[{}]\n{}\n{}\\end{{lstlisting}}",
+                        indent,
+                        options.join(", "),
+                        src,
+                        indent
+                    )
+                }
+            }
+            LetterScriptNodeKind::Table => self.render_table(node_id, level),
+            LetterScriptNodeKind::Function { name, parameters } => {
+                format!("{}{}", indent, render_function_comment(name, parameters))
+            }
+            _ => format!("{}{}", indent, self.render_inline_children(node_id)),
+        }
+    }
+
+    fn render_table(&self, node_id: LetterScriptNodeId, level: usize) -> String {
+        let rows = self.tree.get_node(node_id).children();
+        let column_spec = column_alignments(self.tree, node_id)
+            .iter()
+            .map(|alignment| match alignment {
+                Some(Alignment::Center) => 'c',
+                Some(Alignment::Right) => 'r',
+                _ => 'l',
+            })
+            .collect::<String>();
+
+        let indent = self.indent(level + 1);
+        let mut lines = Vec::new();
+        for row_id in rows {
+            let row = self.tree.get_node(*row_id);
+            let cells = row
+                .children()
+                .iter()
+                .map(|cell_id| self.render_inline_children(*cell_id))
+                .collect::<Vec<_>>();
+
+            lines.push(format!("{}{} \\\\", indent, cells.join(" & ")));
+            if let LetterScriptNodeKind::TableHeaderRow = row.kind() {
+                lines.push(format!("{}\\hline", indent));
+            }
+        }
+
+        self.render_environment("tabular", Some(&column_spec), &lines.join("\n"), level)
+    }
+
+    fn render_environment(
+        &self,
+        name: &str,
+        argument: Option<&str>,
+        content: &str,
+        level: usize,
+    ) -> String {
+        let indent = self.indent(level);
+        let argument = argument
+            .map(|argument| format!("{{{}}}", argument))
+            .unwrap_or_default();
+
+        format!(
+            "{}\\begin{{{}}}{}\n{}\n{}\\end{{{}}}",
+            indent, name, argument, content, indent, name
+        )
+    }
+
+    fn render_inline_children(&self, node_id: LetterScriptNodeId) -> String {
+        self.tree
+            .get_node(node_id)
+            .children()
+            .iter()
+            .map(|child_id| self.render_inline(*child_id))
+            .collect()
+    }
+
+    fn render_inline(&self, node_id: LetterScriptNodeId) -> String {
+        match self.tree.get_node(node_id).kind() {
+            LetterScriptNodeKind::Text(text) => escape_latex(text),
+            LetterScriptNodeKind::Bold => {
+                format!("\\textbf{{{}}}", self.render_inline_children(node_id))
+            }
+            LetterScriptNodeKind::Italic => {
+                format!("\\emph{{{}}}", self.render_inline_children(node_id))
+            }
+            LetterScriptNodeKind::Footnote { .. } => {
+                format!("\\footnote{{{}}}", self.render_inline_children(node_id))
+            }
+            LetterScriptNodeKind::Strikethrough => {
+                format!("\\sout{{{}}}", self.render_inline_children(node_id))
+            }
+            LetterScriptNodeKind::Link { target, .. } => format!(
+                "\\href{{{}}}{{{}}}",
+                escape_latex_url(target),
+                self.render_inline_children(node_id)
+            ),
+            LetterScriptNodeKind::Code { .. } => {
+                format!(
+                    "\\texttt{{{}}}",
+                    escape_latex(&plain_text(self.tree, node_id))
+                )
+            }
+            LetterScriptNodeKind::Image { src, .. } => {
+                format!("\\includegraphics{{{}}}", escape_latex_url(src))
+            }
+            LetterScriptNodeKind::LineBreak => "\\newline{}".to_string(),
+            LetterScriptNodeKind::Function { name, parameters } => {
+                format!("{}\n", render_function_comment(name, parameters))
+            }
+            _ => self.render_inline_children(node_id),
+        }
+    }
+
+    fn indent(&self, level: usize) -> String {
+        match self.options.layout() {
+            Layout::Compact => String::new(),
+            Layout::Pretty(indent) => indent.repeat(level),
+        }
+    }
+}
+
+/// Options of the listings package for the code block.
+/// Attributes without equivalent option (like highlighted lines) are dropped.
+fn listing_options(language: Option<&str>, attributes: &HashMap<String, String>) -> Vec<String> {
+    let mut options = Vec::new();
+    if let Some(language) = language.and_then(listings_language) {
+        options.push(format!("language={}", language));
+    }
+    if let Some(title) = attributes.get("title") {
+        options.push(format!("title={{{}}}", escape_latex(title)));
+    }
+    if attributes
+        .get("linenos")
+        .is_some_and(|value| value != "false")
+    {
+        options.push("numbers=left".to_string());
+    }
+
+    options
+}
+
+/// Map the language of a code block to the name the listings package knows it by.
+/// Languages unknown to listings (like Rust) yield `None`, as they would fail the build.
+fn listings_language(language: &str) -> Option<&'static str> {
+    let language = language.to_lowercase();
+    LISTINGS_LANGUAGES
+        .iter()
+        .find(|(aliases, _)| aliases.contains(&language.as_str()))
+        .map(|(_, name)| *name)
+}
+
+/// Letter functions have no LaTeX equivalent, thus we keep them as comment.
+fn render_function_comment(name: &str, parameters: &HashMap<String, String>) -> String {
+    let mut parameters = parameters
+        .iter()
+        .map(|(key, value)| format!("{}: {}", key, value))
+        .collect::<Vec<_>>();
+    parameters.sort();
+
+    if parameters.is_empty() {
+        format!("% #{}", name)
+    } else {
+        format!("% #{}({})", name, parameters.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::render::test_util::{register_text, span};
+
+    use super::*;
+
+    /// Render the tree and keep only the body in between `\begin{document}` and `\end{document}`.
+    fn render_body(tree: &LetterScriptTree) -> String {
+        let result = LatexRenderer::new(tree, &RenderOptions::default()).render();
+
+        let start = result.find("\\begin{document}\n\n").unwrap() + "\\begin{document}\n\n".len();
+        let end = result.find("\n\n\\end{document}").unwrap();
+        result[start..end].to_string()
+    }
+
+    #[test]
+    fn should_render_document() {
+        let mut tree = LetterScriptTree::new();
+        let paragraph_id =
+            tree.register_node(tree.root_id(), LetterScriptNodeKind::Paragraph, span());
+        register_text(&mut tree, paragraph_id, "Hello World");
+
+        assert_eq!(
+            LatexRenderer::new(&tree, &RenderOptions::default()).render(),
+            "\
+\\documentclass{article}
+
+\\usepackage{amssymb}
+\\usepackage{graphicx}
+\\usepackage{hyperref}
+\\usepackage{listings}
+\\usepackage[normalem]{ulem}
+
+\\begin{document}
+
+Hello World
+
+\\end{document}
+"
+        );
+    }
+
+    #[test]
+    fn should_render_sections_by_depth() {
+        let mut tree = LetterScriptTree::new();
+        let heading_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Heading, span());
+        register_text(&mut tree, heading_id, "Title");
+        let section_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Section, span());
+        let heading_id = tree.register_node(section_id, LetterScriptNodeKind::Heading, span());
+        register_text(&mut tree, heading_id, "Subtitle");
+        let paragraph_id = tree.register_node(section_id, LetterScriptNodeKind::Paragraph, span());
+        register_text(&mut tree, paragraph_id, "Text");
+
+        assert_eq!(
+            render_body(&tree),
+            "\
+\\section{Title}
+
+\\subsection{Subtitle}
+
+Text"
+        );
+    }
+
+    #[test]
+    fn should_escape_special_characters_in_text() {
+        let mut tree = LetterScriptTree::new();
+        let paragraph_id =
+            tree.register_node(tree.root_id(), LetterScriptNodeKind::Paragraph, span());
+        register_text(&mut tree, paragraph_id, "100% & $5 for #1_a");
+
+        assert_eq!(render_body(&tree), "100\\% \\& \\$5 for \\#1\\_a");
+    }
+
+    #[test]
+    fn should_render_inline_formatting() {
+        let mut tree = LetterScriptTree::new();
+        let paragraph_id =
+            tree.register_node(tree.root_id(), LetterScriptNodeKind::Paragraph, span());
+        let bold_id = tree.register_node(paragraph_id, LetterScriptNodeKind::Bold, span());
+        register_text(&mut tree, bold_id, "bold ");
+        let italic_id = tree.register_node(bold_id, LetterScriptNodeKind::Italic, span());
+        register_text(&mut tree, italic_id, "italic");
+        let strikethrough_id =
+            tree.register_node(paragraph_id, LetterScriptNodeKind::Strikethrough, span());
+        register_text(&mut tree, strikethrough_id, "struck");
+        let link_id = tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Link {
+                target: "https://example.com/#top".to_string(),
+                title: None,
+            },
+            span(),
+        );
+        register_text(&mut tree, link_id, "link");
+        let code_id = tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Code {
+                language: None,
+                attributes: HashMap::new(),
+            },
+            span(),
+        );
+        register_text(&mut tree, code_id, "a_b");
+        let footnote_id = tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Footnote {
+                label: "1".to_string(),
+            },
+            span(),
+        );
+        register_text(&mut tree, footnote_id, "note");
+
+        assert_eq!(
+            render_body(&tree),
+            "\\textbf{bold \\emph{italic}}\\sout{struck}\\href{https://example.com/\\#top}{link}\\texttt{a\\_b}\\footnote{note}"
+        );
+    }
+
+    #[test]
+    fn should_render_nested_lists() {
+        let mut tree = LetterScriptTree::new();
+        let list_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::List { ordered: false },
+            span(),
+        );
+        let item_id = tree.register_node(
+            list_id,
+            LetterScriptNodeKind::ListItem { checked: None },
+            span(),
+        );
+        register_text(&mut tree, item_id, "A");
+        let nested_list_id = tree.register_node(
+            list_id,
+            LetterScriptNodeKind::List { ordered: true },
+            span(),
+        );
+        let nested_item_id = tree.register_node(
+            nested_list_id,
+            LetterScriptNodeKind::ListItem { checked: None },
+            span(),
+        );
+        register_text(&mut tree, nested_item_id, "B");
+
+        assert_eq!(
+            render_body(&tree),
+            "\
+\\begin{itemize}
+    \\item A
+    \\begin{enumerate}
+        \\item B
+    \\end{enumerate}
+\\end{itemize}"
+        );
+    }
+
+    #[test]
+    fn should_render_table() {
+        let mut tree = LetterScriptTree::new();
+        let table_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Table, span());
+        let header_row_id =
+            tree.register_node(table_id, LetterScriptNodeKind::TableHeaderRow, span());
+        for text in ["Name", "Age"] {
+            let cell_id = tree.register_node(
+                header_row_id,
+                LetterScriptNodeKind::TableCell { alignment: None },
+                span(),
+            );
+            register_text(&mut tree, cell_id, text);
+        }
+        let row_id = tree.register_node(table_id, LetterScriptNodeKind::TableRow, span());
+        for text in ["Max", "42"] {
+            let cell_id = tree.register_node(
+                row_id,
+                LetterScriptNodeKind::TableCell { alignment: None },
+                span(),
+            );
+            register_text(&mut tree, cell_id, text);
+        }
+
+        assert_eq!(
+            render_body(&tree),
+            "\
+\\begin{tabular}{ll}
+    Name & Age \\\\
+    \\hline
+    Max & 42 \\\\
+\\end{tabular}"
+        );
+    }
+
+    #[test]
+    fn should_render_table_column_alignments() {
+        let mut tree = LetterScriptTree::new();
+        let table_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Table, span());
+        let row_id = tree.register_node(table_id, LetterScriptNodeKind::TableRow, span());
+        for alignment in [None, Some(Alignment::Center), Some(Alignment::Right)] {
+            let cell_id = tree.register_node(
+                row_id,
+                LetterScriptNodeKind::TableCell { alignment },
+                span(),
+            );
+            register_text(&mut tree, cell_id, "x");
+        }
+
+        assert_eq!(
+            render_body(&tree),
+            "\
+\\begin{tabular}{lcr}
+    x & x & x \\\\
+\\end{tabular}"
+        );
+    }
+
+    #[test]
+    fn should_render_code_block_title_and_line_numbers() {
+        let mut tree = LetterScriptTree::new();
+        let code_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Code {
+                language: None,
+                attributes: HashMap::from([
+                    ("title".to_string(), "my_file.txt".to_string()),
+                    ("linenos".to_string(), "true".to_string()),
+                    ("highlight".to_string(), "1".to_string()),
+                ]),
+            },
+            span(),
+        );
+        register_text(&mut tree, code_id, "text");
+
+        assert_eq!(
+            render_body(&tree),
+            "\
+\\begin{lstlisting}[title={my\\_file.txt}, numbers=left]
+text
+\\end{lstlisting}"
+        );
+    }
+
+    #[test]
+    fn should_render_code_blocks() {
+        let mut tree = LetterScriptTree::new();
+        let code_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Code {
+                language: Some("python".to_string()),
+                attributes: HashMap::new(),
+            },
+            span(),
+        );
+        register_text(&mut tree, code_id, "def main():\n    print(\"%\")");
+        let code_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Code {
+                language: None,
+                attributes: HashMap::new(),
+            },
+            span(),
+        );
+        register_text(&mut tree, code_id, "$ ls");
+
+        assert_eq!(
+            render_body(&tree),
+            "\
+\\begin{lstlisting}[language=Python]
+def main():
+    print(\"%\")
+\\end{lstlisting}
+
+\\begin{verbatim}
+$ ls
+\\end{verbatim}"
+        );
+    }
+
+    #[test]
+    fn should_omit_language_unknown_to_listings() {
+        let mut tree = LetterScriptTree::new();
+        let code_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Code {
+                language: Some("rust".to_string()),
+                attributes: HashMap::from([("linenos".to_string(), "true".to_string())]),
+            },
+            span(),
+        );
+        register_text(&mut tree, code_id, "fn main() {}");
+
+        assert_eq!(
+            render_body(&tree),
+            "\\begin{lstlisting}[numbers=left]\nfn main() {}\n\\end{lstlisting}"
+        );
+    }
+
+    #[test]
+    fn should_escape_image_source() {
+        let mut tree = LetterScriptTree::new();
+        tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Image {
+                src: "100%}.png".to_string(),
+                title: None,
+            },
+            span(),
+        );
+
+        assert_eq!(
+            render_body(&tree),
+            "\\begin{figure}[htbp]\n    \\centering\n    \\includegraphics{100\\%\\}.png}\n\\end{figure}"
+        );
+    }
+
+    #[test]
+    fn should_render_image_as_figure() {
+        let mut tree = LetterScriptTree::new();
+        let image_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Image {
+                src: "image.png".to_string(),
+                title: None,
+            },
+            span(),
+        );
+        register_text(&mut tree, image_id, "A caption");
+
+        assert_eq!(
+            render_body(&tree),
+            "\
+\\begin{figure}[htbp]
+    \\centering
+    \\includegraphics{image.png}
+    \\caption{A caption}
+\\end{figure}"
+        );
+    }
+
+    #[test]
+    fn should_render_inline_code_and_image_in_quote_as_text() {
+        let mut tree = LetterScriptTree::new();
+        let quote_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Quote, span());
+        register_text(&mut tree, quote_id, "Run ");
+        let code_id = tree.register_node(
+            quote_id,
+            LetterScriptNodeKind::Code {
+                language: None,
+                attributes: HashMap::new(),
+            },
+            span(),
+        );
+        register_text(&mut tree, code_id, "make");
+        register_text(&mut tree, quote_id, " now ");
+        let image_id = tree.register_node(
+            quote_id,
+            LetterScriptNodeKind::Image {
+                src: "smile.png".to_string(),
+                title: None,
+            },
+            span(),
+        );
+        register_text(&mut tree, image_id, "Smile");
+
+        assert_eq!(
+            render_body(&tree),
+            "\
+\\begin{quote}
+    Run \\texttt{make} now \\includegraphics{smile.png}
+\\end{quote}"
+        );
+    }
+
+    #[test]
+    fn should_render_quote_and_horizontal_rule() {
+        let mut tree = LetterScriptTree::new();
+        let quote_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Quote, span());
+        register_text(&mut tree, quote_id, "Quoted");
+        tree.register_node(tree.root_id(), LetterScriptNodeKind::HorizontalRule, span());
+
+        assert_eq!(
+            render_body(&tree),
+            "\
+\\begin{quote}
+    Quoted
+\\end{quote}
+
+\\noindent\\rule{\\linewidth}{0.4pt}"
+        );
+    }
+
+    #[test]
+    fn should_render_function_as_comment() {
+        let mut tree = LetterScriptTree::new();
+        let mut parameters = HashMap::new();
+        parameters.insert("width".to_string(), "100px".to_string());
+        tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Function {
+                name: "image".to_string(),
+                parameters,
+            },
+            span(),
+        );
+
+        assert_eq!(render_body(&tree), "% #image(width: 100px)");
+    }
+}
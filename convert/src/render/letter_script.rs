@@ -0,0 +1,705 @@
+use crate::render::markup::{
+    Attribute, Display, EmptyElementStyle, MarkupElement, MarkupNode, MarkupPrinter,
+};
+use crate::render::options::RenderOptions;
+use crate::transformer::{LetterScriptNodeId, LetterScriptNodeKind, LetterScriptTree};
+
+pub(crate) struct LetterScriptRenderer<'a> {
+    tree: &'a LetterScriptTree,
+    options: &'a RenderOptions,
+}
+
+impl<'a> LetterScriptRenderer<'a> {
+    pub(crate) fn new(tree: &'a LetterScriptTree, options: &'a RenderOptions) -> Self {
+        Self { tree, options }
+    }
+
+    pub(crate) fn render(&self) -> String {
+        let nodes = self.to_markup_nodes(self.tree.root_id());
+
+        MarkupPrinter::new(self.options, EmptyElementStyle::SelfClosing).print(&nodes)
+    }
+
+    fn to_markup_nodes(&self, node_id: LetterScriptNodeId) -> Vec<MarkupNode> {
+        self.tree
+            .get_node(node_id)
+            .children()
+            .iter()
+            .map(|child_id| self.to_markup_node(*child_id))
+            .collect()
+    }
+
+    fn to_markup_node(&self, node_id: LetterScriptNodeId) -> MarkupNode {
+        let kind = self.tree.get_node(node_id).kind();
+        if let LetterScriptNodeKind::Text(text) = kind {
+            return MarkupNode::Text(text.clone());
+        }
+
+        let name = tag_name(kind);
+        let element = if is_self_closing(kind) {
+            MarkupElement::empty(name, display(kind))
+        } else {
+            MarkupElement::new(name, display(kind)).with_children(self.to_markup_nodes(node_id))
+        };
+
+        attributes(kind)
+            .into_iter()
+            .fold(element, |element, (key, value)| {
+                element.with_attribute(key, value)
+            })
+            .into()
+    }
+}
+
+fn tag_name(kind: &LetterScriptNodeKind) -> String {
+    let name = match kind {
+        LetterScriptNodeKind::Root | LetterScriptNodeKind::Text(_) => "",
+        LetterScriptNodeKind::Heading => "heading",
+        LetterScriptNodeKind::Paragraph => "paragraph",
+        LetterScriptNodeKind::Section => "section",
+        LetterScriptNodeKind::Image { .. } => "image",
+        LetterScriptNodeKind::Quote => "quote",
+        LetterScriptNodeKind::List { .. } => "list",
+        LetterScriptNodeKind::ListItem { .. } => "list-item",
+        LetterScriptNodeKind::HorizontalRule => "horizontal-rule",
+        LetterScriptNodeKind::LineBreak => "break",
+        LetterScriptNodeKind::Link { .. } => "link",
+        LetterScriptNodeKind::Bold => "b",
+        LetterScriptNodeKind::Italic => "i",
+        LetterScriptNodeKind::Strikethrough => "s",
+        LetterScriptNodeKind::Footnote { .. } => "footnote",
+        LetterScriptNodeKind::Code { .. } => "code",
+        LetterScriptNodeKind::Table => "table",
+        LetterScriptNodeKind::TableHeaderRow => "table-header-row",
+        LetterScriptNodeKind::TableRow => "table-row",
+        LetterScriptNodeKind::TableCell { .. } => "table-cell",
+        LetterScriptNodeKind::Function { name, .. } => name,
+    };
+
+    name.to_string()
+}
+
+fn attributes(kind: &LetterScriptNodeKind) -> Vec<Attribute> {
+    match kind {
+        LetterScriptNodeKind::Image { src, title } => with_title(("src", src), title),
+        LetterScriptNodeKind::List { ordered } => {
+            if *ordered {
+                vec![("ordered".to_string(), "true".to_string())]
+            } else {
+                Vec::new()
+            }
+        }
+        LetterScriptNodeKind::ListItem { checked } => checked
+            .iter()
+            .map(|checked| ("checked".to_string(), checked.to_string()))
+            .collect(),
+        LetterScriptNodeKind::Link { target, title } => with_title(("target", target), title),
+        LetterScriptNodeKind::TableCell { alignment } => alignment
+            .iter()
+            .map(|alignment| ("align".to_string(), alignment.name().to_string()))
+            .collect(),
+        LetterScriptNodeKind::Footnote { label } => vec![("label".to_string(), label.clone())],
+        LetterScriptNodeKind::Code {
+            language,
+            attributes,
+        } => {
+            let mut entries = attributes
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect::<Vec<_>>();
+            entries.sort();
+            if let Some(language) = language {
+                entries.insert(0, ("language".to_string(), language.clone()));
+            }
+            entries
+        }
+        LetterScriptNodeKind::Function { parameters, .. } => {
+            let mut entries = parameters
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect::<Vec<_>>();
+            entries.sort();
+            entries
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Attributes of a link or image, followed by its title if any.
+fn with_title((name, value): (&str, &str), title: &Option<String>) -> Vec<Attribute> {
+    let mut attributes = vec![(name.to_string(), value.to_string())];
+    if let Some(title) = title {
+        attributes.push(("title".to_string(), title.clone()));
+    }
+    attributes
+}
+
+fn is_self_closing(kind: &LetterScriptNodeKind) -> bool {
+    matches!(
+        kind,
+        LetterScriptNodeKind::HorizontalRule | LetterScriptNodeKind::LineBreak
+    )
+}
+
+fn display(kind: &LetterScriptNodeKind) -> Display {
+    match kind {
+        LetterScriptNodeKind::Text(_)
+        | LetterScriptNodeKind::Bold
+        | LetterScriptNodeKind::Italic
+        | LetterScriptNodeKind::Strikethrough
+        | LetterScriptNodeKind::Footnote { .. }
+        | LetterScriptNodeKind::LineBreak
+        | LetterScriptNodeKind::Link { .. } => Display::Inline,
+        LetterScriptNodeKind::Heading
+        | LetterScriptNodeKind::Paragraph
+        | LetterScriptNodeKind::ListItem { .. }
+        | LetterScriptNodeKind::TableCell { .. }
+        | LetterScriptNodeKind::Image { .. }
+        | LetterScriptNodeKind::Code { .. }
+        | LetterScriptNodeKind::Function { .. } => Display::TextBlock,
+        LetterScriptNodeKind::Quote => Display::MixedBlock,
+        _ => Display::Block,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::render::options::Indent;
+    use crate::render::test_util::span;
+
+    use super::*;
+
+    fn render(tree: &LetterScriptTree) -> String {
+        LetterScriptRenderer::new(tree, &RenderOptions::default()).render()
+    }
+
+    fn render_single(kind: LetterScriptNodeKind, text: Option<&str>) -> String {
+        let mut tree = LetterScriptTree::new();
+        let node_id = tree.register_node(tree.root_id(), kind, span());
+        if let Some(text) = text {
+            tree.register_node(
+                node_id,
+                LetterScriptNodeKind::Text(text.to_string()),
+                span(),
+            );
+        }
+
+        render(&tree)
+    }
+
+    /// Build a section containing a heading and a paragraph with mixed formatting.
+    fn create_section_tree() -> LetterScriptTree {
+        let mut tree = LetterScriptTree::new();
+        let section_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Section, span());
+        let heading_id = tree.register_node(section_id, LetterScriptNodeKind::Heading, span());
+        tree.register_node(
+            heading_id,
+            LetterScriptNodeKind::Text("Subtitle".to_string()),
+            span(),
+        );
+        let paragraph_id = tree.register_node(section_id, LetterScriptNodeKind::Paragraph, span());
+        tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Text("Here is  some ".to_string()),
+            span(),
+        );
+        let bold_id = tree.register_node(paragraph_id, LetterScriptNodeKind::Bold, span());
+        tree.register_node(
+            bold_id,
+            LetterScriptNodeKind::Text("bold".to_string()),
+            span(),
+        );
+        tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Text(".".to_string()),
+            span(),
+        );
+
+        tree
+    }
+
+    #[test]
+    fn should_render_empty_tree() {
+        let tree = LetterScriptTree::new();
+
+        assert_eq!(render(&tree), "");
+    }
+
+    #[test]
+    fn should_render_text() {
+        assert_eq!(
+            render_single(LetterScriptNodeKind::Text("Hello World".to_string()), None),
+            "Hello World"
+        );
+    }
+
+    #[test]
+    fn should_render_heading() {
+        assert_eq!(
+            render_single(LetterScriptNodeKind::Heading, Some("Title")),
+            "<heading>Title</heading>\n"
+        );
+    }
+
+    #[test]
+    fn should_render_paragraph() {
+        assert_eq!(
+            render_single(LetterScriptNodeKind::Paragraph, Some("Some text")),
+            "<paragraph>Some text</paragraph>\n"
+        );
+    }
+
+    #[test]
+    fn should_render_section() {
+        assert_eq!(
+            render(&create_section_tree()),
+            "\
+<section>
+    <heading>Subtitle</heading>
+    <paragraph>Here is  some <b>bold</b>.</paragraph>
+</section>
+"
+        );
+    }
+
+    #[test]
+    fn should_render_image() {
+        assert_eq!(
+            render_single(
+                LetterScriptNodeKind::Image {
+                    src: "image.png".to_string(),
+                    title: None,
+                },
+                Some("Alt text")
+            ),
+            "<image src=\"image.png\">Alt text</image>\n"
+        );
+    }
+
+    #[test]
+    fn should_render_quote() {
+        let mut tree = LetterScriptTree::new();
+        let quote_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Quote, span());
+        let nested_quote_id = tree.register_node(quote_id, LetterScriptNodeKind::Quote, span());
+        tree.register_node(
+            nested_quote_id,
+            LetterScriptNodeKind::Text("Quoted".to_string()),
+            span(),
+        );
+
+        assert_eq!(
+            render(&tree),
+            "\
+<quote>
+    <quote>Quoted</quote>
+</quote>
+"
+        );
+    }
+
+    #[test]
+    fn should_render_text_and_nested_blocks_of_quotes_on_own_lines() {
+        let mut tree = LetterScriptTree::new();
+        let quote_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Quote, span());
+        tree.register_node(
+            quote_id,
+            LetterScriptNodeKind::Text("Line one ".to_string()),
+            span(),
+        );
+        let bold_id = tree.register_node(quote_id, LetterScriptNodeKind::Bold, span());
+        tree.register_node(
+            bold_id,
+            LetterScriptNodeKind::Text("two".to_string()),
+            span(),
+        );
+        let nested_quote_id = tree.register_node(quote_id, LetterScriptNodeKind::Quote, span());
+        tree.register_node(
+            nested_quote_id,
+            LetterScriptNodeKind::Text("Nested".to_string()),
+            span(),
+        );
+        tree.register_node(
+            quote_id,
+            LetterScriptNodeKind::Text("Back".to_string()),
+            span(),
+        );
+
+        assert_eq!(
+            render(&tree),
+            "\
+<quote>
+    Line one <b>two</b>
+    <quote>Nested</quote>
+    Back
+</quote>
+"
+        );
+    }
+
+    #[test]
+    fn should_render_unordered_list() {
+        let mut tree = LetterScriptTree::new();
+        let list_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::List { ordered: false },
+            span(),
+        );
+        let item_id = tree.register_node(
+            list_id,
+            LetterScriptNodeKind::ListItem { checked: None },
+            span(),
+        );
+        tree.register_node(
+            item_id,
+            LetterScriptNodeKind::Text("Item".to_string()),
+            span(),
+        );
+
+        assert_eq!(
+            render(&tree),
+            "\
+<list>
+    <list-item>Item</list-item>
+</list>
+"
+        );
+    }
+
+    #[test]
+    fn should_render_ordered_list() {
+        assert_eq!(
+            render_single(LetterScriptNodeKind::List { ordered: true }, None),
+            "<list ordered=\"true\"></list>\n"
+        );
+    }
+
+    #[test]
+    fn should_render_list_item() {
+        assert_eq!(
+            render_single(
+                LetterScriptNodeKind::ListItem { checked: None },
+                Some("Item")
+            ),
+            "<list-item>Item</list-item>\n"
+        );
+    }
+
+    #[test]
+    fn should_render_horizontal_rule() {
+        assert_eq!(
+            render_single(LetterScriptNodeKind::HorizontalRule, None),
+            "<horizontal-rule/>\n"
+        );
+    }
+
+    #[test]
+    fn should_render_link() {
+        assert_eq!(
+            render_single(
+                LetterScriptNodeKind::Link {
+                    target: "https://example.com".to_string(),
+                    title: None,
+                },
+                Some("Example")
+            ),
+            "<link target=\"https://example.com\">Example</link>"
+        );
+    }
+
+    #[test]
+    fn should_render_bold() {
+        assert_eq!(
+            render_single(LetterScriptNodeKind::Bold, Some("bold")),
+            "<b>bold</b>"
+        );
+    }
+
+    #[test]
+    fn should_render_italic() {
+        assert_eq!(
+            render_single(LetterScriptNodeKind::Italic, Some("italic")),
+            "<i>italic</i>"
+        );
+    }
+
+    #[test]
+    fn should_render_strikethrough() {
+        assert_eq!(
+            render_single(LetterScriptNodeKind::Strikethrough, Some("struck")),
+            "<s>struck</s>"
+        );
+    }
+
+    #[test]
+    fn should_render_footnote() {
+        assert_eq!(
+            render_single(
+                LetterScriptNodeKind::Footnote {
+                    label: "note".to_string()
+                },
+                Some("Details")
+            ),
+            "<footnote label=\"note\">Details</footnote>"
+        );
+    }
+
+    #[test]
+    fn should_render_code_without_language() {
+        assert_eq!(
+            render_single(
+                LetterScriptNodeKind::Code {
+                    language: None,
+                    attributes: HashMap::new()
+                },
+                Some("x")
+            ),
+            "<code>x</code>\n"
+        );
+    }
+
+    #[test]
+    fn should_render_code_with_language() {
+        assert_eq!(
+            render_single(
+                LetterScriptNodeKind::Code {
+                    language: Some("js".to_string()),
+                    attributes: HashMap::new()
+                },
+                Some("console.log('Hello');\n    console.log('World');")
+            ),
+            "\
+<code language=\"js\">console.log('Hello');
+    console.log('World');</code>
+"
+        );
+    }
+
+    #[test]
+    fn should_render_code_attributes_after_language() {
+        assert_eq!(
+            render_single(
+                LetterScriptNodeKind::Code {
+                    language: Some("rust".to_string()),
+                    attributes: HashMap::from([
+                        ("title".to_string(), "main.rs".to_string()),
+                        ("linenos".to_string(), "true".to_string()),
+                    ]),
+                },
+                Some("fn main() {}")
+            ),
+            "<code language=\"rust\" linenos=\"true\" title=\"main.rs\">fn main() {}</code>\n"
+        );
+    }
+
+    #[test]
+    fn should_render_table() {
+        let mut tree = LetterScriptTree::new();
+        let table_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Table, span());
+        let header_row_id =
+            tree.register_node(table_id, LetterScriptNodeKind::TableHeaderRow, span());
+        let header_cell_id = tree.register_node(
+            header_row_id,
+            LetterScriptNodeKind::TableCell { alignment: None },
+            span(),
+        );
+        tree.register_node(
+            header_cell_id,
+            LetterScriptNodeKind::Text("Header".to_string()),
+            span(),
+        );
+        let row_id = tree.register_node(table_id, LetterScriptNodeKind::TableRow, span());
+        let cell_id = tree.register_node(
+            row_id,
+            LetterScriptNodeKind::TableCell { alignment: None },
+            span(),
+        );
+        tree.register_node(
+            cell_id,
+            LetterScriptNodeKind::Text("Cell".to_string()),
+            span(),
+        );
+
+        assert_eq!(
+            render(&tree),
+            "\
+<table>
+    <table-header-row>
+        <table-cell>Header</table-cell>
+    </table-header-row>
+    <table-row>
+        <table-cell>Cell</table-cell>
+    </table-row>
+</table>
+"
+        );
+    }
+
+    #[test]
+    fn should_render_function_with_sorted_parameters() {
+        assert_eq!(
+            render_single(
+                LetterScriptNodeKind::Function {
+                    name: "image".to_string(),
+                    parameters: HashMap::from([
+                        ("width".to_string(), "100px".to_string()),
+                        ("src".to_string(), "image.png".to_string()),
+                    ]),
+                },
+                None
+            ),
+            "<image src=\"image.png\" width=\"100px\"></image>\n"
+        );
+    }
+
+    #[test]
+    fn should_escape_text_content() {
+        assert_eq!(
+            render_single(
+                LetterScriptNodeKind::Paragraph,
+                Some("1 < 2 & \"quoted\" > 0")
+            ),
+            "<paragraph>1 &lt; 2 &amp; \"quoted\" &gt; 0</paragraph>\n"
+        );
+    }
+
+    #[test]
+    fn should_escape_attribute_values() {
+        assert_eq!(
+            render_single(
+                LetterScriptNodeKind::Link {
+                    target: "https://example.com/?q=\"a\"&b=<c>".to_string(),
+                    title: None,
+                },
+                Some("Link")
+            ),
+            "<link target=\"https://example.com/?q=&quot;a&quot;&amp;b=&lt;c&gt;\">Link</link>"
+        );
+    }
+
+    #[test]
+    fn should_escape_function_parameters() {
+        assert_eq!(
+            render_single(
+                LetterScriptNodeKind::Function {
+                    name: "note".to_string(),
+                    parameters: HashMap::from([(
+                        "text".to_string(),
+                        "Say \"Hi\" & leave".to_string()
+                    )]),
+                },
+                None
+            ),
+            "<note text=\"Say &quot;Hi&quot; &amp; leave\"></note>\n"
+        );
+    }
+
+    #[test]
+    fn should_skip_invalid_function_parameter_keys() {
+        assert_eq!(
+            render_single(
+                LetterScriptNodeKind::Function {
+                    name: "note".to_string(),
+                    parameters: HashMap::from([
+                        ("a b".to_string(), "c".to_string()),
+                        ("\"x y\"".to_string(), "1".to_string()),
+                        ("text".to_string(), "Hi".to_string()),
+                    ]),
+                },
+                None
+            ),
+            "<note text=\"Hi\"></note>\n"
+        );
+    }
+
+    #[test]
+    fn should_mangle_invalid_function_names() {
+        assert_eq!(
+            render_single(
+                LetterScriptNodeKind::Function {
+                    name: "my note".to_string(),
+                    parameters: HashMap::new(),
+                },
+                None
+            ),
+            "<my-note></my-note>\n"
+        );
+    }
+
+    #[test]
+    fn should_render_compact() {
+        let tree = create_section_tree();
+        let options = RenderOptions::compact();
+
+        assert_eq!(
+            LetterScriptRenderer::new(&tree, &options).render(),
+            "<section><heading>Subtitle</heading><paragraph>Here is  some <b>bold</b>.</paragraph></section>"
+        );
+    }
+
+    #[test]
+    fn should_render_pretty_with_custom_indent_width() {
+        let tree = create_section_tree();
+        let options = RenderOptions::pretty(Indent::Spaces(2));
+
+        assert_eq!(
+            LetterScriptRenderer::new(&tree, &options).render(),
+            "\
+<section>
+  <heading>Subtitle</heading>
+  <paragraph>Here is  some <b>bold</b>.</paragraph>
+</section>
+"
+        );
+    }
+
+    #[test]
+    fn should_render_pretty_with_tabs() {
+        let tree = create_section_tree();
+        let options = RenderOptions::pretty(Indent::Tabs);
+
+        assert_eq!(
+            LetterScriptRenderer::new(&tree, &options).render(),
+            "<section>\n\t<heading>Subtitle</heading>\n\t<paragraph>Here is  some <b>bold</b>.</paragraph>\n</section>\n"
+        );
+    }
+
+    #[test]
+    fn should_preserve_inline_whitespace() {
+        let mut tree = LetterScriptTree::new();
+        let paragraph_id =
+            tree.register_node(tree.root_id(), LetterScriptNodeKind::Paragraph, span());
+        let italic_id = tree.register_node(paragraph_id, LetterScriptNodeKind::Italic, span());
+        tree.register_node(
+            italic_id,
+            LetterScriptNodeKind::Text(" leading and trailing ".to_string()),
+            span(),
+        );
+        let code_id = tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Code {
+                language: None,
+                attributes: HashMap::new(),
+            },
+            span(),
+        );
+        tree.register_node(
+            code_id,
+            LetterScriptNodeKind::Text("a  b".to_string()),
+            span(),
+        );
+
+        for options in [
+            RenderOptions::default(),
+            RenderOptions::compact(),
+            RenderOptions::pretty(Indent::Tabs),
+        ] {
+            assert!(LetterScriptRenderer::new(&tree, &options)
+                .render()
+                .starts_with(
+                    "<paragraph><i> leading and trailing </i><code>a  b</code></paragraph>"
+                ));
+        }
+    }
+}
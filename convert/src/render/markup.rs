@@ -0,0 +1,299 @@
+//! Intermediate representation of XML-like markup (Letter script, HTML)
+//! and the printer laying it out according to the render options.
+
+use crate::render::escape::{escape_attribute_value, escape_text, is_markup_name, to_markup_name};
+use crate::render::options::{Layout, RenderOptions};
+
+pub(crate) type Attribute = (String, String);
+
+pub(crate) enum MarkupNode {
+    Text(String),
+    Element(MarkupElement),
+}
+
+pub(crate) struct MarkupElement {
+    name: String,
+    attributes: Vec<Attribute>,
+    children: Vec<MarkupNode>,
+    display: Display,
+    is_empty: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum Display {
+    /// Block on its own line that may contain further blocks.
+    Block,
+
+    /// Block on its own line that contains running text only.
+    TextBlock,
+
+    /// Block on its own line whose running text may be interrupted by further blocks, like quotes.
+    /// Each run of text and each block is placed on its own line.
+    MixedBlock,
+
+    /// Part of the running text of its parent.
+    Inline,
+}
+
+/// How elements that cannot have any content are closed.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum EmptyElementStyle {
+    /// For example `<horizontal-rule/>`.
+    SelfClosing,
+
+    /// For example `<hr>`.
+    Void,
+}
+
+impl MarkupElement {
+    pub(crate) fn new(name: impl Into<String>, display: Display) -> Self {
+        Self {
+            name: name.into(),
+            attributes: Vec::new(),
+            children: Vec::new(),
+            display,
+            is_empty: false,
+        }
+    }
+
+    /// Create an element that cannot have any content.
+    pub(crate) fn empty(name: impl Into<String>, display: Display) -> Self {
+        Self {
+            is_empty: true,
+            ..Self::new(name, display)
+        }
+    }
+
+    pub(crate) fn with_attribute(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.attributes.push((key.into(), value.into()));
+        self
+    }
+
+    pub(crate) fn with_children(mut self, children: Vec<MarkupNode>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    pub(crate) fn push_child(&mut self, child: MarkupNode) {
+        self.children.push(child);
+    }
+}
+
+impl From<MarkupElement> for MarkupNode {
+    fn from(element: MarkupElement) -> Self {
+        MarkupNode::Element(element)
+    }
+}
+
+impl MarkupNode {
+    fn is_inline(&self) -> bool {
+        match self {
+            MarkupNode::Text(_) => true,
+            MarkupNode::Element(element) => element.display == Display::Inline,
+        }
+    }
+}
+
+pub(crate) struct MarkupPrinter<'a> {
+    options: &'a RenderOptions,
+    empty_element_style: EmptyElementStyle,
+}
+
+impl<'a> MarkupPrinter<'a> {
+    pub(crate) fn new(options: &'a RenderOptions, empty_element_style: EmptyElementStyle) -> Self {
+        Self {
+            options,
+            empty_element_style,
+        }
+    }
+
+    pub(crate) fn print(&self, nodes: &[MarkupNode]) -> String {
+        let mut result = String::new();
+
+        let is_inline = self.has_inline_layout(Display::Block, nodes);
+        for node in nodes {
+            self.print_node(node, &mut result, 0, is_inline);
+        }
+
+        result
+    }
+
+    fn print_node(&self, node: &MarkupNode, result: &mut String, level: usize, is_inline: bool) {
+        if !is_inline {
+            self.push_indent(result, level);
+        }
+
+        match node {
+            MarkupNode::Text(text) => result.push_str(&escape_text(text)),
+            MarkupNode::Element(element) => self.print_element(element, result, level, is_inline),
+        }
+
+        if !is_inline {
+            result.push('\n');
+        }
+    }
+
+    fn print_element(
+        &self,
+        element: &MarkupElement,
+        result: &mut String,
+        level: usize,
+        is_inline: bool,
+    ) {
+        // Names may stem from user input, thus invalid element names are mangled and invalid attributes skipped
+        let name = to_markup_name(&element.name);
+        result.push('<');
+        result.push_str(&name);
+        for (key, value) in &element.attributes {
+            if is_markup_name(key) {
+                result.push_str(&format!(" {}=\"{}\"", key, escape_attribute_value(value)));
+            }
+        }
+
+        if element.is_empty {
+            match self.empty_element_style {
+                EmptyElementStyle::SelfClosing => result.push_str("/>"),
+                EmptyElementStyle::Void => result.push('>'),
+            }
+            return;
+        }
+
+        result.push('>');
+
+        // Once within inline content, whitespace must not be added anymore
+        if is_inline || self.has_inline_layout(element.display, &element.children) {
+            for child in &element.children {
+                self.print_node(child, result, level + 1, true);
+            }
+        } else {
+            result.push('\n');
+            let mut children = element.children.iter().peekable();
+            while let Some(child) = children.next() {
+                if child.is_inline() {
+                    // Whitespace within a run of text is part of the text, thus the run stays on one line
+                    self.push_indent(result, level + 1);
+                    self.print_node(child, result, level + 1, true);
+                    while let Some(next) = children.next_if(|next| next.is_inline()) {
+                        self.print_node(next, result, level + 1, true);
+                    }
+                    result.push('\n');
+                } else {
+                    self.print_node(child, result, level + 1, false);
+                }
+            }
+            self.push_indent(result, level);
+        }
+
+        result.push_str(&format!("</{}>", name));
+    }
+
+    /// Whether the given children of an element are to be rendered on a single line.
+    /// Inline content must never be surrounded by additional whitespace,
+    /// as that would change the text of the document.
+    fn has_inline_layout(&self, display: Display, children: &[MarkupNode]) -> bool {
+        match self.options.layout() {
+            Layout::Compact => true,
+            Layout::Pretty(_) => match display {
+                Display::Block => {
+                    children.is_empty() || children.iter().any(|child| child.is_inline())
+                }
+                Display::MixedBlock => children.iter().all(|child| child.is_inline()),
+                Display::TextBlock | Display::Inline => true,
+            },
+        }
+    }
+
+    fn push_indent(&self, result: &mut String, level: usize) {
+        if let Layout::Pretty(indent) = self.options.layout() {
+            result.push_str(&indent.repeat(level));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::render::options::Indent;
+
+    use super::*;
+
+    fn create_nodes() -> Vec<MarkupNode> {
+        vec![MarkupElement::new("section", Display::Block)
+            .with_children(vec![
+                MarkupElement::new("heading", Display::TextBlock)
+                    .with_children(vec![MarkupNode::Text("Title".to_string())])
+                    .into(),
+                MarkupElement::empty("rule", Display::Block).into(),
+                MarkupElement::new("paragraph", Display::TextBlock)
+                    .with_children(vec![
+                        MarkupNode::Text("A & B ".to_string()),
+                        MarkupElement::new("b", Display::Inline)
+                            .with_attribute("title", "\"C\"")
+                            .with_children(vec![MarkupNode::Text("C".to_string())])
+                            .into(),
+                    ])
+                    .into(),
+            ])
+            .into()]
+    }
+
+    #[test]
+    fn should_print_pretty() {
+        let options = RenderOptions::pretty(Indent::Spaces(2));
+        let printer = MarkupPrinter::new(&options, EmptyElementStyle::SelfClosing);
+
+        assert_eq!(
+            printer.print(&create_nodes()),
+            "\
+<section>
+  <heading>Title</heading>
+  <rule/>
+  <paragraph>A &amp; B <b title=\"&quot;C&quot;\">C</b></paragraph>
+</section>
+"
+        );
+    }
+
+    #[test]
+    fn should_print_text_and_blocks_of_mixed_block_on_own_lines() {
+        let options = RenderOptions::pretty(Indent::Spaces(2));
+        let printer = MarkupPrinter::new(&options, EmptyElementStyle::SelfClosing);
+        let nodes = vec![MarkupElement::new("quote", Display::MixedBlock)
+            .with_children(vec![
+                MarkupNode::Text("Line one ".to_string()),
+                MarkupElement::new("b", Display::Inline)
+                    .with_children(vec![MarkupNode::Text("two".to_string())])
+                    .into(),
+                MarkupElement::new("quote", Display::MixedBlock)
+                    .with_children(vec![MarkupNode::Text("Nested".to_string())])
+                    .into(),
+                MarkupNode::Text("Back".to_string()),
+            ])
+            .into()];
+
+        assert_eq!(
+            printer.print(&nodes),
+            "\
+<quote>
+  Line one <b>two</b>
+  <quote>Nested</quote>
+  Back
+</quote>
+"
+        );
+    }
+
+    #[test]
+    fn should_print_compact_with_void_elements() {
+        let options = RenderOptions::compact();
+        let printer = MarkupPrinter::new(&options, EmptyElementStyle::Void);
+
+        assert_eq!(
+            printer.print(&create_nodes()),
+            "<section><heading>Title</heading><rule><paragraph>A &amp; B <b title=\"&quot;C&quot;\">C</b></paragraph></section>"
+        );
+    }
+}
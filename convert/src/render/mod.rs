@@ -1 +1,39 @@
-//! Render the Letter document to the letter script format.
+//! Render the Letter document to one of the supported output formats.
+
+pub use format::Format;
+pub(crate) use html::HtmlRenderer;
+pub(crate) use json::JsonRenderer;
+pub(crate) use latex::LatexRenderer;
+pub(crate) use letter_script::LetterScriptRenderer;
+pub use options::{Indent, Layout, RenderOptions};
+pub(crate) use typst::TypstRenderer;
+
+use crate::transformer::LetterScriptTree;
+
+mod escape;
+mod format;
+mod html;
+mod json;
+mod latex;
+mod letter_script;
+mod markup;
+mod options;
+mod table;
+#[cfg(test)]
+pub(crate) mod test_util;
+mod text;
+mod typst;
+
+pub(crate) fn render_tree(
+    tree: &LetterScriptTree,
+    format: Format,
+    options: &RenderOptions,
+) -> String {
+    match format {
+        Format::LetterScript => LetterScriptRenderer::new(tree, options).render(),
+        Format::Html => HtmlRenderer::new(tree, options).render(),
+        Format::Latex => LatexRenderer::new(tree, options).render(),
+        Format::Typst => TypstRenderer::new(tree, options).render(),
+        Format::Json => JsonRenderer::new(tree, options).render(),
+    }
+}
@@ -0,0 +1,57 @@
+/// Options controlling how a document is laid out when rendered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderOptions {
+    layout: Layout,
+}
+
+/// Layout of the rendered markup.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Layout {
+    /// Render everything on one line without adding any whitespace.
+    Compact,
+
+    /// Render block elements on their own lines, indented by their nesting depth.
+    /// Elements containing inline content (paragraphs, headings, ...) are kept on one line.
+    Pretty(Indent),
+}
+
+/// Indentation used per nesting level in the pretty layout.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Indent {
+    Spaces(usize),
+    Tabs,
+}
+
+impl RenderOptions {
+    pub fn new(layout: Layout) -> Self {
+        Self { layout }
+    }
+
+    pub fn compact() -> Self {
+        Self::new(Layout::Compact)
+    }
+
+    pub fn pretty(indent: Indent) -> Self {
+        Self::new(Layout::Pretty(indent))
+    }
+
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self::pretty(Indent::Spaces(4))
+    }
+}
+
+impl Indent {
+    /// Indentation of the given nesting level.
+    pub(crate) fn repeat(&self, level: usize) -> String {
+        match self {
+            Indent::Spaces(count) => " ".repeat(count * level),
+            Indent::Tabs => "\t".repeat(level),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::transformer::{LetterScriptNodeId, LetterScriptNodeKind, LetterScriptTree};
+use crate::util::Alignment;
+
+/// Alignment of each column of the given table, taken from the first row having a cell in that column.
+pub(crate) fn column_alignments(
+    tree: &LetterScriptTree,
+    table_node_id: LetterScriptNodeId,
+) -> Vec<Option<Alignment>> {
+    let mut alignments: Vec<Option<Alignment>> = Vec::new();
+
+    for row_id in tree.get_node(table_node_id).children() {
+        for (column_index, cell_id) in tree.get_node(*row_id).children().iter().enumerate() {
+            if column_index < alignments.len() {
+                continue;
+            }
+
+            let alignment = match tree.get_node(*cell_id).kind() {
+                LetterScriptNodeKind::TableCell { alignment } => *alignment,
+                _ => None,
+            };
+            alignments.push(alignment);
+        }
+    }
+
+    alignments
+}
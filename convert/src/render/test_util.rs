@@ -0,0 +1,20 @@
+//! Helpers to build Letter document trees in the tests of the render backends.
+
+use crate::transformer::{LetterScriptNodeId, LetterScriptNodeKind, LetterScriptTree};
+use crate::util::{SourcePosition, SourceSpan};
+
+pub(crate) fn span() -> SourceSpan {
+    SourceSpan::new(SourcePosition::zero(), SourcePosition::zero())
+}
+
+pub(crate) fn register_text(
+    tree: &mut LetterScriptTree,
+    parent_id: LetterScriptNodeId,
+    text: &str,
+) -> LetterScriptNodeId {
+    tree.register_node(
+        parent_id,
+        LetterScriptNodeKind::Text(text.to_string()),
+        span(),
+    )
+}
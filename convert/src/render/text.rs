@@ -0,0 +1,76 @@
+//! Text related helpers shared by the render backends.
+
+use crate::transformer::{LetterScriptNodeId, LetterScriptNodeKind, LetterScriptTree};
+
+/// Collect the text of the node and all its descendants without any formatting.
+pub(crate) fn plain_text(tree: &LetterScriptTree, node_id: LetterScriptNodeId) -> String {
+    let node = tree.get_node(node_id);
+    if let LetterScriptNodeKind::Text(text) = node.kind() {
+        return text.clone();
+    }
+
+    node.children()
+        .iter()
+        .map(|child_id| plain_text(tree, *child_id))
+        .collect()
+}
+
+/// Check whether the given child is part of the running text of its parent.
+/// Quotes contain their text directly, so all but nested quotes is running text there, like in paragraphs.
+pub(crate) fn is_running_text(parent: &LetterScriptNodeKind, child: &LetterScriptNodeKind) -> bool {
+    match parent {
+        LetterScriptNodeKind::Quote => !matches!(child, LetterScriptNodeKind::Quote),
+        _ => is_inline_content(child),
+    }
+}
+
+fn is_inline_content(kind: &LetterScriptNodeKind) -> bool {
+    matches!(
+        kind,
+        LetterScriptNodeKind::Text(_)
+            | LetterScriptNodeKind::Bold
+            | LetterScriptNodeKind::Italic
+            | LetterScriptNodeKind::Strikethrough
+            | LetterScriptNodeKind::Footnote { .. }
+            | LetterScriptNodeKind::LineBreak
+            | LetterScriptNodeKind::Link { .. }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::render::test_util::{register_text, span};
+
+    use super::*;
+
+    #[test]
+    fn should_collect_plain_text_of_descendants() {
+        let mut tree = LetterScriptTree::new();
+        let paragraph_id =
+            tree.register_node(tree.root_id(), LetterScriptNodeKind::Paragraph, span());
+        register_text(&mut tree, paragraph_id, "Some ");
+        let bold_id = tree.register_node(paragraph_id, LetterScriptNodeKind::Bold, span());
+        register_text(&mut tree, bold_id, "bold");
+
+        assert_eq!(plain_text(&tree, paragraph_id), "Some bold");
+    }
+
+    #[test]
+    fn should_treat_all_but_nested_quotes_as_running_text_of_quotes() {
+        let code = LetterScriptNodeKind::Code {
+            language: None,
+            attributes: Default::default(),
+        };
+
+        assert!(is_running_text(&LetterScriptNodeKind::Quote, &code));
+        assert!(!is_running_text(
+            &LetterScriptNodeKind::Quote,
+            &LetterScriptNodeKind::Quote
+        ));
+        assert!(!is_running_text(&LetterScriptNodeKind::Root, &code));
+        assert!(is_running_text(
+            &LetterScriptNodeKind::Root,
+            &LetterScriptNodeKind::Bold
+        ));
+    }
+}
@@ -0,0 +1,669 @@
+use std::collections::HashMap;
+
+use crate::render::escape::{escape_typst, escape_typst_string};
+use crate::render::options::{Layout, RenderOptions};
+use crate::render::table::column_alignments;
+use crate::render::text::{is_running_text, plain_text};
+use crate::transformer::{LetterScriptNodeId, LetterScriptNodeKind, LetterScriptTree};
+
+/// Indentation of nested list items.
+/// It is part of the Typst syntax and thus not affected by the render options.
+const LIST_INDENT: &str = "  ";
+
+/// Units of Typst numeric values that are passed to functions without quotes.
+const NUMERIC_UNITS: [&str; 10] = ["", "pt", "mm", "cm", "in", "em", "%", "fr", "deg", "rad"];
+
+/// Keywords of the Typst code mode, which cannot be used as function names or parameter keys.
+const TYPST_KEYWORDS: [&str; 22] = [
+    "none", "auto", "true", "false", "not", "and", "or", "let", "set", "show", "context", "if",
+    "else", "for", "in", "while", "break", "continue", "return", "import", "include", "as",
+];
+
+/// Render the Letter document as Typst markup.
+pub(crate) struct TypstRenderer<'a> {
+    tree: &'a LetterScriptTree,
+    options: &'a RenderOptions,
+}
+
+impl<'a> TypstRenderer<'a> {
+    pub(crate) fn new(tree: &'a LetterScriptTree, options: &'a RenderOptions) -> Self {
+        Self { tree, options }
+    }
+
+    pub(crate) fn render(&self) -> String {
+        let blocks = self.render_blocks(self.tree.root_id(), 0);
+        if blocks.is_empty() {
+            return String::new();
+        }
+
+        format!("{}\n", blocks.join("\n\n"))
+    }
+
+    /// Render the children of the given node as blocks.
+    /// Consecutive inline children (for example text in a quote) are combined into a single block.
+    fn render_blocks(&self, node_id: LetterScriptNodeId, section_depth: usize) -> Vec<String> {
+        let mut blocks = Vec::new();
+        let mut text = String::new();
+
+        let kind = self.tree.get_node(node_id).kind();
+        for child_id in self.tree.get_node(node_id).children() {
+            if is_running_text(kind, self.tree.get_node(*child_id).kind()) {
+                text.push_str(&self.render_inline(*child_id));
+                continue;
+            }
+
+            if !text.is_empty() {
+                blocks.push(std::mem::take(&mut text));
+            }
+
+            blocks.push(self.render_block(*child_id, section_depth));
+        }
+
+        if !text.is_empty() {
+            blocks.push(text);
+        }
+
+        blocks
+    }
+
+    fn render_block(&self, node_id: LetterScriptNodeId, section_depth: usize) -> String {
+        match self.tree.get_node(node_id).kind() {
+            LetterScriptNodeKind::Section => {
+                self.render_blocks(node_id, section_depth + 1).join("\n\n")
+            }
+            LetterScriptNodeKind::Heading => format!(
+                "{} {}",
+                "=".repeat(section_depth + 1),
+                self.render_inline_children(node_id)
+            ),
+            LetterScriptNodeKind::Image { src, .. } => format!(
+                "#image(\"{}\", alt: \"{}\")",
+                escape_typst_string(src),
+                escape_typst_string(&plain_text(self.tree, node_id))
+            ),
+            LetterScriptNodeKind::Quote => {
+                let content = self.render_blocks(node_id, section_depth).join("\n\n");
+                format!("#quote(block: true){}", self.render_content_block(&content))
+            }
+            LetterScriptNodeKind::List { .. } => self.render_list(node_id, 0).join("\n"),
+            LetterScriptNodeKind::HorizontalRule => "#line(length: 100%)".to_string(),
+            LetterScriptNodeKind::Code { language, .. } => {
+                let src = plain_text(self.tree, node_id);
+                let fence = "`".repeat(longest_backtick_run(&src).max(2) + 1);
+
+                format!(
+                    "{}{}\n{}\n{}",
+                    fence,
+                    language.as_deref().unwrap_or_default(),
+                    src,
+                    fence
+                )
+            }
+            LetterScriptNodeKind::Table => self.render_table(node_id),
+            LetterScriptNodeKind::Function { name, parameters } => {
+                render_function_call(name, parameters)
+            }
+            _ => self.render_inline_children(node_id),
+        }
+    }
+
+    fn render_list(&self, list_node_id: LetterScriptNodeId, level: usize) -> Vec<String> {
+        let marker = match self.tree.get_node(list_node_id).kind() {
+            LetterScriptNodeKind::List { ordered: true } => "+",
+            _ => "-",
+        };
+
+        let mut lines = Vec::new();
+        for child_id in self.tree.get_node(list_node_id).children() {
+            if let LetterScriptNodeKind::List { .. } = self.tree.get_node(*child_id).kind() {
+                lines.extend(self.render_list(*child_id, level + 1));
+            } else {
+                let checkbox = match self.tree.get_node(*child_id).kind() {
+                    LetterScriptNodeKind::ListItem {
+                        checked: Some(true),
+                    } => "☒ ",
+                    LetterScriptNodeKind::ListItem {
+                        checked: Some(false),
+                    } => "☐ ",
+                    _ => "",
+                };
+                lines.push(format!(
+                    "{}{} {}{}",
+                    LIST_INDENT.repeat(level),
+                    marker,
+                    checkbox,
+                    self.render_inline_children(*child_id)
+                ));
+            }
+        }
+
+        lines
+    }
+
+    fn render_table(&self, node_id: LetterScriptNodeId) -> String {
+        let rows = self.tree.get_node(node_id).children();
+        let alignments = column_alignments(self.tree, node_id);
+
+        let mut arguments = vec![format!("columns: {}", alignments.len())];
+        if alignments.iter().any(Option::is_some) {
+            let alignments = alignments
+                .iter()
+                .map(|alignment| alignment.map_or("auto", |alignment| alignment.name()))
+                .collect::<Vec<_>>();
+
+            // A Typst array with a single element needs a trailing comma
+            let separator = if alignments.len() == 1 { "," } else { "" };
+            arguments.push(format!("align: ({}{})", alignments.join(", "), separator));
+        }
+        for row_id in rows {
+            let row = self.tree.get_node(*row_id);
+            let cells = row
+                .children()
+                .iter()
+                .map(|cell_id| format!("[{}]", self.render_inline_children(*cell_id)))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            if let LetterScriptNodeKind::TableHeaderRow = row.kind() {
+                arguments.push(format!("table.header({})", cells));
+            } else {
+                arguments.push(cells);
+            }
+        }
+
+        match self.options.layout() {
+            Layout::Compact => format!("#table({})", arguments.join(", ")),
+            Layout::Pretty(indent) => {
+                let indent = indent.repeat(1);
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| format!("{}{},\n", indent, argument))
+                    .collect::<String>();
+
+                format!("#table(\n{})", arguments)
+            }
+        }
+    }
+
+    /// Wrap the given content in a Typst content block (`[...]`).
+    fn render_content_block(&self, content: &str) -> String {
+        match self.options.layout() {
+            Layout::Compact => format!("[{}]", content),
+            Layout::Pretty(indent) => {
+                let indent = indent.repeat(1);
+                let content = content
+                    .lines()
+                    .map(|line| {
+                        if line.is_empty() {
+                            "\n".to_string()
+                        } else {
+                            format!("{}{}\n", indent, line)
+                        }
+                    })
+                    .collect::<String>();
+
+                format!("[\n{}]", content)
+            }
+        }
+    }
+
+    fn render_inline_children(&self, node_id: LetterScriptNodeId) -> String {
+        self.tree
+            .get_node(node_id)
+            .children()
+            .iter()
+            .map(|child_id| self.render_inline(*child_id))
+            .collect()
+    }
+
+    fn render_inline(&self, node_id: LetterScriptNodeId) -> String {
+        match self.tree.get_node(node_id).kind() {
+            LetterScriptNodeKind::Text(text) => escape_typst(text),
+            LetterScriptNodeKind::Bold => {
+                format!("#strong[{}]", self.render_inline_children(node_id))
+            }
+            LetterScriptNodeKind::Italic => {
+                format!("#emph[{}]", self.render_inline_children(node_id))
+            }
+            LetterScriptNodeKind::Footnote { .. } => {
+                format!("#footnote[{}]", self.render_inline_children(node_id))
+            }
+            LetterScriptNodeKind::Strikethrough => {
+                format!("#strike[{}]", self.render_inline_children(node_id))
+            }
+            LetterScriptNodeKind::Link { target, .. } => format!(
+                "#link(\"{}\")[{}]",
+                escape_typst_string(target),
+                self.render_inline_children(node_id)
+            ),
+            LetterScriptNodeKind::Code { .. } => format!(
+                "#raw(\"{}\")",
+                escape_typst_string(&plain_text(self.tree, node_id))
+            ),
+            LetterScriptNodeKind::Image { src, .. } => format!(
+                "#box(image(\"{}\", alt: \"{}\"))",
+                escape_typst_string(src),
+                escape_typst_string(&plain_text(self.tree, node_id))
+            ),
+            LetterScriptNodeKind::LineBreak => "#linebreak()".to_string(),
+            LetterScriptNodeKind::Function { name, parameters } => {
+                render_function_call(name, parameters)
+            }
+            _ => self.render_inline_children(node_id),
+        }
+    }
+}
+
+/// Map a Letter function to a Typst function call.
+/// Numeric values (for example `100pt` or `50%`) and booleans are passed as is, everything else as string.
+/// Parameters whose keys are no Typst identifiers are skipped.
+/// Functions whose names cannot be called in Typst are kept as comment, like in LaTeX.
+fn render_function_call(name: &str, parameters: &HashMap<String, String>) -> String {
+    let mut arguments = parameters
+        .iter()
+        .filter(|(key, _)| is_typst_identifier(key))
+        .map(|(key, value)| {
+            if is_typst_literal(value) {
+                format!("{}: {}", key, value)
+            } else {
+                format!("{}: \"{}\"", key, escape_typst_string(value))
+            }
+        })
+        .collect::<Vec<_>>();
+    arguments.sort();
+
+    if is_typst_identifier(name) {
+        format!("#{}({})", name, arguments.join(", "))
+    } else {
+        // Block comments nest in Typst, thus their delimiters must not appear within
+        let call = format!("#{}({})", name, arguments.join(", "))
+            .replace("/*", "/ *")
+            .replace("*/", "* /");
+        format!("/* {} */", call)
+    }
+}
+
+/// Check whether the given name can be used as Typst identifier, which excludes keywords.
+fn is_typst_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    let is_valid = match chars.next() {
+        Some(first) if first.is_alphabetic() || first == '_' => {
+            chars.all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        }
+        _ => false,
+    };
+
+    is_valid && !TYPST_KEYWORDS.contains(&name)
+}
+
+fn is_typst_literal(value: &str) -> bool {
+    if value == "true" || value == "false" {
+        return true;
+    }
+
+    let number_length = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(number_length);
+
+    number.parse::<f64>().is_ok() && NUMERIC_UNITS.contains(&unit)
+}
+
+fn longest_backtick_run(src: &str) -> usize {
+    src.split(|c| c != '`')
+        .map(|run| run.len())
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::render::test_util::{register_text, span};
+    use crate::util::Alignment;
+
+    use super::*;
+
+    fn render(tree: &LetterScriptTree) -> String {
+        TypstRenderer::new(tree, &RenderOptions::default()).render()
+    }
+
+    #[test]
+    fn should_render_empty_tree() {
+        assert_eq!(render(&LetterScriptTree::new()), "");
+    }
+
+    #[test]
+    fn should_render_headings_by_section_depth() {
+        let mut tree = LetterScriptTree::new();
+        let heading_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Heading, span());
+        register_text(&mut tree, heading_id, "Title");
+        let section_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Section, span());
+        let heading_id = tree.register_node(section_id, LetterScriptNodeKind::Heading, span());
+        register_text(&mut tree, heading_id, "Subtitle");
+        let paragraph_id = tree.register_node(section_id, LetterScriptNodeKind::Paragraph, span());
+        register_text(&mut tree, paragraph_id, "Text");
+
+        assert_eq!(
+            render(&tree),
+            "\
+= Title
+
+== Subtitle
+
+Text
+"
+        );
+    }
+
+    #[test]
+    fn should_render_inline_formatting() {
+        let mut tree = LetterScriptTree::new();
+        let paragraph_id =
+            tree.register_node(tree.root_id(), LetterScriptNodeKind::Paragraph, span());
+        register_text(&mut tree, paragraph_id, "Costs $5 ");
+        let bold_id = tree.register_node(paragraph_id, LetterScriptNodeKind::Bold, span());
+        register_text(&mut tree, bold_id, "bold ");
+        let italic_id = tree.register_node(bold_id, LetterScriptNodeKind::Italic, span());
+        register_text(&mut tree, italic_id, "italic");
+        let strikethrough_id =
+            tree.register_node(paragraph_id, LetterScriptNodeKind::Strikethrough, span());
+        register_text(&mut tree, strikethrough_id, "struck");
+        let link_id = tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Link {
+                target: "https://example.com".to_string(),
+                title: None,
+            },
+            span(),
+        );
+        register_text(&mut tree, link_id, "link");
+        let code_id = tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Code {
+                language: None,
+                attributes: HashMap::new(),
+            },
+            span(),
+        );
+        register_text(&mut tree, code_id, "\"x\"");
+        let footnote_id = tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Footnote {
+                label: "1".to_string(),
+            },
+            span(),
+        );
+        register_text(&mut tree, footnote_id, "note");
+
+        assert_eq!(
+            render(&tree),
+            "Costs \\$5 #strong[bold #emph[italic]]#strike[struck]#link(\"https://example.com\")[link]#raw(\"\\\"x\\\"\")#footnote[note]\n"
+        );
+    }
+
+    #[test]
+    fn should_render_nested_lists() {
+        let mut tree = LetterScriptTree::new();
+        let list_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::List { ordered: false },
+            span(),
+        );
+        let item_id = tree.register_node(
+            list_id,
+            LetterScriptNodeKind::ListItem { checked: None },
+            span(),
+        );
+        register_text(&mut tree, item_id, "A");
+        let nested_list_id = tree.register_node(
+            list_id,
+            LetterScriptNodeKind::List { ordered: true },
+            span(),
+        );
+        let nested_item_id = tree.register_node(
+            nested_list_id,
+            LetterScriptNodeKind::ListItem { checked: None },
+            span(),
+        );
+        register_text(&mut tree, nested_item_id, "B");
+        let item_id = tree.register_node(
+            list_id,
+            LetterScriptNodeKind::ListItem { checked: None },
+            span(),
+        );
+        register_text(&mut tree, item_id, "C");
+
+        assert_eq!(
+            render(&tree),
+            "\
+- A
+  + B
+- C
+"
+        );
+    }
+
+    #[test]
+    fn should_render_table() {
+        let mut tree = LetterScriptTree::new();
+        let table_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Table, span());
+        let header_row_id =
+            tree.register_node(table_id, LetterScriptNodeKind::TableHeaderRow, span());
+        for text in ["Name", "Age"] {
+            let cell_id = tree.register_node(
+                header_row_id,
+                LetterScriptNodeKind::TableCell { alignment: None },
+                span(),
+            );
+            register_text(&mut tree, cell_id, text);
+        }
+        let row_id = tree.register_node(table_id, LetterScriptNodeKind::TableRow, span());
+        for text in ["Max", "42"] {
+            let cell_id = tree.register_node(
+                row_id,
+                LetterScriptNodeKind::TableCell { alignment: None },
+                span(),
+            );
+            register_text(&mut tree, cell_id, text);
+        }
+
+        assert_eq!(
+            render(&tree),
+            "\
+#table(
+    columns: 2,
+    table.header([Name], [Age]),
+    [Max], [42],
+)
+"
+        );
+        assert_eq!(
+            TypstRenderer::new(&tree, &RenderOptions::compact()).render(),
+            "#table(columns: 2, table.header([Name], [Age]), [Max], [42])\n"
+        );
+    }
+
+    #[test]
+    fn should_render_table_column_alignments() {
+        let mut tree = LetterScriptTree::new();
+        let table_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Table, span());
+        let row_id = tree.register_node(table_id, LetterScriptNodeKind::TableRow, span());
+        for alignment in [None, Some(Alignment::Center), Some(Alignment::Right)] {
+            let cell_id = tree.register_node(
+                row_id,
+                LetterScriptNodeKind::TableCell { alignment },
+                span(),
+            );
+            register_text(&mut tree, cell_id, "x");
+        }
+
+        assert_eq!(
+            TypstRenderer::new(&tree, &RenderOptions::compact()).render(),
+            "#table(columns: 3, align: (auto, center, right), [x], [x], [x])\n"
+        );
+    }
+
+    #[test]
+    fn should_render_raw_block_with_language() {
+        let mut tree = LetterScriptTree::new();
+        let code_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Code {
+                language: Some("rust".to_string()),
+                attributes: HashMap::new(),
+            },
+            span(),
+        );
+        register_text(&mut tree, code_id, "let s = \"```\";");
+
+        assert_eq!(render(&tree), "````rust\nlet s = \"```\";\n````\n");
+    }
+
+    #[test]
+    fn should_render_image() {
+        let mut tree = LetterScriptTree::new();
+        let image_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Image {
+                src: "image.png".to_string(),
+                title: None,
+            },
+            span(),
+        );
+        register_text(&mut tree, image_id, "Alt text");
+
+        assert_eq!(render(&tree), "#image(\"image.png\", alt: \"Alt text\")\n");
+    }
+
+    #[test]
+    fn should_render_inline_code_and_image_in_quote_as_text() {
+        let mut tree = LetterScriptTree::new();
+        let quote_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Quote, span());
+        register_text(&mut tree, quote_id, "Run ");
+        let code_id = tree.register_node(
+            quote_id,
+            LetterScriptNodeKind::Code {
+                language: None,
+                attributes: HashMap::new(),
+            },
+            span(),
+        );
+        register_text(&mut tree, code_id, "make");
+        register_text(&mut tree, quote_id, " now ");
+        let image_id = tree.register_node(
+            quote_id,
+            LetterScriptNodeKind::Image {
+                src: "smile.png".to_string(),
+                title: None,
+            },
+            span(),
+        );
+        register_text(&mut tree, image_id, "Smile");
+
+        assert_eq!(
+            render(&tree),
+            "\
+#quote(block: true)[
+    Run #raw(\"make\") now #box(image(\"smile.png\", alt: \"Smile\"))
+]
+"
+        );
+    }
+
+    #[test]
+    fn should_render_quote_and_horizontal_rule() {
+        let mut tree = LetterScriptTree::new();
+        let quote_id = tree.register_node(tree.root_id(), LetterScriptNodeKind::Quote, span());
+        register_text(&mut tree, quote_id, "Quoted");
+        tree.register_node(tree.root_id(), LetterScriptNodeKind::HorizontalRule, span());
+
+        assert_eq!(
+            render(&tree),
+            "\
+#quote(block: true)[
+    Quoted
+]
+
+#line(length: 100%)
+"
+        );
+    }
+
+    #[test]
+    fn should_render_function_as_call() {
+        let mut tree = LetterScriptTree::new();
+        let mut parameters = HashMap::new();
+        parameters.insert("width".to_string(), "100pt".to_string());
+        parameters.insert("src".to_string(), "image.png".to_string());
+        tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Function {
+                name: "image".to_string(),
+                parameters,
+            },
+            span(),
+        );
+        tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Function {
+                name: "pagebreak".to_string(),
+                parameters: HashMap::new(),
+            },
+            span(),
+        );
+
+        assert_eq!(
+            render(&tree),
+            "\
+#image(src: \"image.png\", width: 100pt)
+
+#pagebreak()
+"
+        );
+    }
+
+    #[test]
+    fn should_skip_parameters_that_are_no_identifiers() {
+        let mut tree = LetterScriptTree::new();
+        let parameters = HashMap::from([
+            ("\"x y\"".to_string(), "1".to_string()),
+            ("a b".to_string(), "c".to_string()),
+            ("in".to_string(), "d".to_string()),
+            ("src".to_string(), "a.png".to_string()),
+        ]);
+        tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Function {
+                name: "image".to_string(),
+                parameters,
+            },
+            span(),
+        );
+
+        assert_eq!(render(&tree), "#image(src: \"a.png\")\n");
+    }
+
+    #[test]
+    fn should_render_function_named_like_keyword_as_comment() {
+        let mut tree = LetterScriptTree::new();
+        let paragraph_id =
+            tree.register_node(tree.root_id(), LetterScriptNodeKind::Paragraph, span());
+        register_text(&mut tree, paragraph_id, "Before ");
+        tree.register_node(
+            paragraph_id,
+            LetterScriptNodeKind::Function {
+                name: "break".to_string(),
+                parameters: HashMap::from([("note".to_string(), "a */ b".to_string())]),
+            },
+            span(),
+        );
+        register_text(&mut tree, paragraph_id, " after");
+
+        assert_eq!(
+            render(&tree),
+            "Before /* #break(note: \"a * / b\") */ after\n"
+        );
+    }
+}
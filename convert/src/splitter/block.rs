@@ -11,6 +11,7 @@ impl SplitterBlock {
         Self { src, span }
     }
 
+    #[cfg(test)]
     pub(crate) fn src(&self) -> &str {
         &self.src
     }
@@ -1,14 +1,18 @@
 //! Split a string (or file) into blocks of text that are separated by one or more empty lines.
+//! The underline of a setext heading ends its block as well.
 
 use std::collections::VecDeque;
+use std::io;
 use std::io::{BufReader, Read};
 
 use utf8_chars::BufReadCharsExt;
 
 pub(crate) use block::SplitterBlock;
 
+use crate::categorizer::setext_heading_level;
 use crate::util::SourcePosition;
 use crate::util::SourceSpan;
+use crate::util::{indentation_width, CODE_INDENT_WIDTH};
 
 mod block;
 
@@ -17,6 +21,7 @@ pub(crate) struct BlockSplitter {
     unread_chars_buffer: VecDeque<char>,
     last_char_source_position: SourcePosition,
     next_char_source_position: SourcePosition,
+    error: Option<io::Error>,
 }
 
 impl BlockSplitter {
@@ -26,6 +31,7 @@ impl BlockSplitter {
             unread_chars_buffer: VecDeque::new(),
             last_char_source_position: SourcePosition::zero(),
             next_char_source_position: SourcePosition::zero(),
+            error: None,
         }
     }
 
@@ -33,7 +39,7 @@ impl BlockSplitter {
         let next_char = self.unread_chars_buffer.pop_front();
 
         let update_source_position = next_char.is_none();
-        let next_char = next_char.or_else(|| self.reader.read_char().ok().flatten());
+        let next_char = next_char.or_else(|| self.read_char_from_reader());
 
         if let Some(c) = next_char {
             if c == '\r' {
@@ -55,6 +61,27 @@ impl BlockSplitter {
         next_char
     }
 
+    /// Read the next char from the underlying reader.
+    /// Reading stops at the first error, which is kept to be retrieved using `take_error`.
+    fn read_char_from_reader(&mut self) -> Option<char> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        match self.reader.read_char() {
+            Ok(c) => c,
+            Err(e) => {
+                self.error = Some(e);
+                None
+            }
+        }
+    }
+
+    /// Take the error that stopped reading the source, if any.
+    pub(crate) fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
     pub fn last_char_source_position(&self) -> SourcePosition {
         self.last_char_source_position.clone()
     }
@@ -68,6 +95,43 @@ impl BlockSplitter {
     }
 }
 
+/// Run of consecutive code fence chars like "```" or "~~~~".
+struct FenceRun {
+    c: char,
+    length: usize,
+
+    /// Whether the run starts the block and may thus open a fenced code block.
+    is_block_start: bool,
+}
+
+/// Trim the blank lines and whitespace around the block.
+/// Code blocks keep the indentation of their first line, as it is stripped from all their lines.
+fn trim_block(src: &str, is_code: bool) -> String {
+    if !is_code {
+        return src.trim().to_string();
+    }
+
+    let content_start = src.find(|c: char| !c.is_whitespace()).unwrap_or(0);
+    let line_start = src[..content_start]
+        .rfind('\n')
+        .map(|index| index + 1)
+        .unwrap_or(0);
+
+    src[line_start..].trim_end().to_string()
+}
+
+/// Check whether the last line of the given source underlines the text before it as setext heading.
+fn ends_with_setext_underline(src: &str) -> bool {
+    match src.trim_end().rsplit_once('\n') {
+        Some((content, underline)) => {
+            !content.trim().is_empty()
+                && indentation_width(underline) < CODE_INDENT_WIDTH
+                && setext_heading_level(underline).is_some()
+        }
+        None => false,
+    }
+}
+
 impl Iterator for BlockSplitter {
     type Item = SplitterBlock;
 
@@ -76,12 +140,30 @@ impl Iterator for BlockSplitter {
         let mut end_position = self.next_char_source_position();
         let mut buffer = String::new();
         let mut newline_count = 0;
-        let mut consecutive_backtick_counter = 0;
-        let mut in_code_block = false;
+        let mut fence_run: Option<FenceRun> = None;
+        let mut open_fence: Option<(char, usize)> = None;
+        let mut in_indented_code_block = false;
+        let mut in_fenced_code_block = false;
 
         loop {
             let next_char = self.read_next_char();
 
+            // A run of fence chars ends with the first different char
+            if fence_run.as_ref().map(|run| Some(run.c)) != Some(next_char) {
+                if let Some(run) = fence_run.take() {
+                    match open_fence {
+                        Some((c, length)) if run.c == c && run.length >= length => {
+                            open_fence = None;
+                        }
+                        None if run.is_block_start && run.length >= 3 => {
+                            open_fence = Some((run.c, run.length));
+                            in_fenced_code_block = true;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
             match next_char {
                 None => {
                     return if buffer.is_empty() {
@@ -104,30 +186,49 @@ impl Iterator for BlockSplitter {
                         buffer.push(c);
                     }
                     _ => {
-                        if newline_count >= 2 && !in_code_block {
+                        let line_start = buffer.rfind('\n').map(|index| index + 1).unwrap_or(0);
+                        let indentation = indentation_width(&buffer[line_start..]);
+
+                        // Indented code blocks continue after empty lines as long as they are indented
+                        let continues_indented_code_block =
+                            in_indented_code_block && indentation >= CODE_INDENT_WIDTH;
+                        let is_block_end = newline_count >= 2
+                            || (newline_count == 1
+                                && !in_indented_code_block
+                                && ends_with_setext_underline(&buffer[..line_start]));
+                        if is_block_end && open_fence.is_none() && !continues_indented_code_block {
+                            // The indentation of the line belongs to the next block
+                            for c in buffer[line_start..].chars() {
+                                self.push_unread_char(c);
+                            }
                             self.push_unread_char(c);
 
-                            let trimmed_string = buffer.trim().to_string();
+                            let trimmed_string = trim_block(
+                                &buffer[..line_start],
+                                in_indented_code_block || in_fenced_code_block,
+                            );
                             return Some(SplitterBlock::new(
                                 trimmed_string,
                                 SourceSpan::new(start_position, end_position),
                             ));
                         }
 
-                        if c == '`' {
-                            if buffer.is_empty()
-                                || consecutive_backtick_counter > 0
-                                || in_code_block
-                            {
-                                consecutive_backtick_counter += 1;
+                        let is_block_start = buffer.trim().is_empty();
+                        if is_block_start {
+                            in_indented_code_block = indentation >= CODE_INDENT_WIDTH;
+                        }
 
-                                if consecutive_backtick_counter == 3 {
-                                    in_code_block = !in_code_block;
-                                    consecutive_backtick_counter = 0;
+                        if c == '`' || c == '~' {
+                            match fence_run.as_mut() {
+                                Some(run) => run.length += 1,
+                                None => {
+                                    fence_run = Some(FenceRun {
+                                        c,
+                                        length: 1,
+                                        is_block_start: is_block_start && !in_indented_code_block,
+                                    })
                                 }
                             }
-                        } else {
-                            consecutive_backtick_counter = 0;
                         }
 
                         newline_count = 0;
@@ -198,6 +299,50 @@ The paragraph is finalized by this sentence."#
         assert!(splitter.next().is_none());
     }
 
+    #[test]
+    fn end_block_after_setext_underline() {
+        let src = "Multi-line\ntitle\n===\nText right below.\n---\nMore text";
+
+        let mut splitter = BlockSplitter::new(Box::new(src.as_bytes()));
+
+        let heading_block = splitter.next().unwrap();
+        assert_eq!(heading_block.src(), "Multi-line\ntitle\n===");
+        assert_eq!(heading_block.span().end.line, 3);
+        assert_eq!(heading_block.span().end.column, 4);
+
+        let second_heading_block = splitter.next().unwrap();
+        assert_eq!(second_heading_block.src(), "Text right below.\n---");
+        assert_eq!(second_heading_block.span().start.line, 4);
+        assert_eq!(second_heading_block.span().start.column, 1);
+
+        assert_eq!(splitter.next().unwrap().src(), "More text");
+        assert!(splitter.next().is_none());
+    }
+
+    #[test]
+    fn keep_setext_underline_in_code_block() {
+        let src = "```\ncode\n---\nmore code\n```";
+
+        let mut splitter = BlockSplitter::new(Box::new(src.as_bytes()));
+
+        assert_eq!(splitter.next().unwrap().src(), src);
+        assert!(splitter.next().is_none());
+    }
+
+    #[test]
+    fn should_keep_read_error() {
+        let src: &[u8] = &[b'a', 0xff, b'b'];
+
+        let mut splitter = BlockSplitter::new(Box::new(src));
+
+        assert_eq!(splitter.next().unwrap().src(), "a");
+        assert!(splitter.next().is_none());
+        assert_eq!(
+            splitter.take_error().unwrap().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
     #[test]
     fn empty_source() {
         let src = "";
@@ -279,4 +424,46 @@ console.log(test);
 
         assert!(splitter.next().is_none());
     }
+
+    #[test]
+    fn allow_empty_lines_in_tilde_and_long_fences() {
+        let src = "~~~\na\n\nb\n~~~\n\n````md\n```\n\n```\n````\n\nText";
+
+        let mut splitter = BlockSplitter::new(Box::new(src.as_bytes()));
+
+        assert_eq!(splitter.next().unwrap().src(), "~~~\na\n\nb\n~~~");
+        assert_eq!(splitter.next().unwrap().src(), "````md\n```\n\n```\n````");
+        assert_eq!(splitter.next().unwrap().src(), "Text");
+        assert!(splitter.next().is_none());
+    }
+
+    #[test]
+    fn keep_indentation_of_indented_fence() {
+        let src = "Text\n\n  ```\n  code\n\n  ```\n\n   ~~~\n   more code\n   ~~~";
+
+        let mut splitter = BlockSplitter::new(Box::new(src.as_bytes()));
+
+        assert_eq!(splitter.next().unwrap().src(), "Text");
+        assert_eq!(splitter.next().unwrap().src(), "  ```\n  code\n\n  ```");
+        assert_eq!(
+            splitter.next().unwrap().src(),
+            "   ~~~\n   more code\n   ~~~"
+        );
+        assert!(splitter.next().is_none());
+    }
+
+    #[test]
+    fn keep_indentation_of_indented_code_block() {
+        let src = "Text\n\n    let a = 1;\n\n        let b = 2;\n\nMore text";
+
+        let mut splitter = BlockSplitter::new(Box::new(src.as_bytes()));
+
+        assert_eq!(splitter.next().unwrap().src(), "Text");
+        let block = splitter.next().unwrap();
+        assert_eq!(block.src(), "    let a = 1;\n\n        let b = 2;");
+        assert_eq!(block.span().start.line, 3);
+        assert_eq!(block.span().end.line, 5);
+        assert_eq!(splitter.next().unwrap().src(), "More text");
+        assert!(splitter.next().is_none());
+    }
 }
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use crate::parser::LinkReferenceDefinition;
+use crate::util::SourceSpan;
+use crate::warning::{Warning, WarningCode};
+
+/// Link reference definitions of the whole document to resolve reference-style links and images with.
+pub(crate) struct LinkReferences {
+    targets: HashMap<String, (String, Option<String>)>,
+    warnings: Vec<Warning>,
+}
+
+impl LinkReferences {
+    /// Collect the given definitions, where the first definition of a label wins.
+    pub(crate) fn new<'a>(definitions: impl Iterator<Item = &'a LinkReferenceDefinition>) -> Self {
+        let mut targets = HashMap::new();
+        for definition in definitions {
+            targets
+                .entry(normalize_label(definition.label()))
+                .or_insert_with(|| {
+                    (
+                        definition.target().to_string(),
+                        definition.title().map(|title| title.to_string()),
+                    )
+                });
+        }
+
+        Self {
+            targets,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Find the target and title of the given reference, noting a warning if it is not defined.
+    pub(crate) fn resolve(
+        &mut self,
+        reference: &str,
+        span: &SourceSpan,
+    ) -> Option<(String, Option<String>)> {
+        let target = self.targets.get(&normalize_label(reference)).cloned();
+
+        if target.is_none() {
+            self.warnings.push(Warning::new(
+                WarningCode::UndefinedLinkReference,
+                format!(
+                    "Link reference '{}' is not defined and is thus kept as text",
+                    reference
+                ),
+                span.clone(),
+            ));
+        }
+
+        target
+    }
+
+    pub(crate) fn into_warnings(self) -> Vec<Warning> {
+        self.warnings
+    }
+}
+
+/// Labels are matched case-insensitively with consecutive whitespace collapsed.
+fn normalize_label(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
@@ -1,37 +1,216 @@
 //! Transform the parsed blocks into a Letter document model tree.
 
+use std::collections::{HashMap, HashSet};
+
 use crate::parser::{
-    CodeBlock, FunctionBlock, HeadingBlock, ImageBlock, ListBlock, ListNodeId, ListNodeKind,
-    ListNodeStyle, ListTree, ParsedBlock, ParsedBlockKind, QuoteBlock, QuoteNodeId, QuoteNodeKind,
-    QuoteTree, TableBlock, TableCell, TableRow, TextBlock, TextNodeId, TextNodeKind, TextTree,
+    CodeBlock, FootnoteDefinition, FunctionBlock, HeadingBlock, ImageBlock, ListBlock, ListNodeId,
+    ListNodeKind, ListNodeStyle, ListTree, ParsedBlock, ParsedBlockKind, QuoteBlock, QuoteNodeId,
+    QuoteNodeKind, QuoteTree, TableBlock, TableCell, TableRow, TextBlock, TextNodeId, TextNodeKind,
+    TextTree,
+};
+use crate::transformer::link_reference::LinkReferences;
+pub(crate) use crate::transformer::result::{TransformError, Transformed};
+pub use crate::transformer::tree::{
+    LetterScriptNode, LetterScriptNodeId, LetterScriptNodeKind, LetterScriptTree,
 };
-use crate::transformer::result::TransformResult;
-use crate::transformer::tree::{LetterScriptNodeId, LetterScriptNodeKind, LetterScriptTree};
-use crate::util::SourceSpan;
+use crate::util::{Alignment, SourceSpan};
+use crate::warning::{Warning, WarningCode};
 
+mod link_reference;
 mod result;
 mod tree;
 
-pub(crate) fn transform(
-    blocks: impl Iterator<Item = ParsedBlock>,
-) -> TransformResult<LetterScriptTree> {
+/// Transform the given blocks into a tree, returning it along with the warnings and errors found.
+pub(crate) fn transform(blocks: impl Iterator<Item = ParsedBlock>) -> Transformed {
     let mut tree = LetterScriptTree::new();
+    let mut warnings = Vec::new();
+    let mut errors = Vec::new();
 
-    transform_blocks(&mut tree, blocks);
+    // Link reference definitions may follow their usages, so collect them upfront
+    let blocks = blocks.collect::<Vec<_>>();
 
-    Ok(tree)
+    // The root spans the whole document
+    if let Some(end) = blocks.iter().map(|block| block.span().end.clone()).max() {
+        let root_id = tree.root_id();
+        tree.get_node_mut(root_id).span_mut().end = end;
+    }
+    let mut link_references =
+        LinkReferences::new(blocks.iter().flat_map(|block| match block.kind() {
+            ParsedBlockKind::LinkReference(link_reference_block) => {
+                link_reference_block.definitions()
+            }
+            _ => &[],
+        }));
+
+    let footnote_definitions =
+        transform_blocks(&mut tree, &mut warnings, &mut link_references, blocks);
+    link_footnotes(
+        &mut tree,
+        &mut errors,
+        &mut link_references,
+        footnote_definitions,
+    );
+    warnings.extend(link_references.into_warnings());
+
+    Transformed::new(tree, warnings, errors)
 }
 
-fn transform_blocks(tree: &mut LetterScriptTree, blocks: impl Iterator<Item = ParsedBlock>) {
+/// Transform the given blocks, setting aside the footnote definitions to link them later on.
+fn transform_blocks(
+    tree: &mut LetterScriptTree,
+    warnings: &mut Vec<Warning>,
+    link_references: &mut LinkReferences,
+    blocks: Vec<ParsedBlock>,
+) -> Vec<FootnoteDefinition> {
     let mut node_stack = vec![tree.root_id()];
+    let mut footnote_definitions = Vec::new();
 
     for block in blocks {
-        transform_block(tree, &mut node_stack, block);
+        if block.is_link_reference() {
+            continue;
+        }
+
+        if block.is_footnote() {
+            if let ParsedBlockKind::Footnote(footnote_block) = block.into_kind() {
+                footnote_definitions.extend(footnote_block.into_definitions());
+            }
+            continue;
+        }
+
+        transform_block(tree, warnings, link_references, &mut node_stack, block);
+    }
+
+    footnote_definitions
+}
+
+/// Fill the footnote nodes created for footnote references with the content of their definitions.
+fn link_footnotes(
+    tree: &mut LetterScriptTree,
+    errors: &mut Vec<TransformError>,
+    link_references: &mut LinkReferences,
+    definitions: Vec<FootnoteDefinition>,
+) {
+    let mut definitions_by_label: HashMap<&str, &FootnoteDefinition> = HashMap::new();
+    for definition in &definitions {
+        if definitions_by_label.contains_key(definition.label()) {
+            errors.push(TransformError {
+                message: format!(
+                    "Footnote '{}' is defined more than once",
+                    definition.label()
+                ),
+                span: definition.span().clone(),
+                help: Some("Give every footnote definition a unique label".to_string()),
+            });
+        } else {
+            definitions_by_label.insert(definition.label(), definition);
+        }
+    }
+
+    // Definitions may reference further footnotes, thus the references within filled footnotes are linked as well
+    let mut referenced_labels = HashSet::new();
+    let mut pending = find_footnote_nodes(tree, tree.root_id())
+        .into_iter()
+        .rev()
+        .map(|node_id| (node_id, Vec::new()))
+        .collect::<Vec<(LetterScriptNodeId, Vec<String>)>>();
+    while let Some((node_id, enclosing_labels)) = pending.pop() {
+        let node = tree.get_node(node_id);
+        let label = if let LetterScriptNodeKind::Footnote { label } = node.kind() {
+            label.clone()
+        } else {
+            unreachable!()
+        };
+
+        if enclosing_labels.contains(&label) {
+            errors.push(TransformError {
+                message: format!("Footnote '{}' references itself", label),
+                span: node.span().clone(),
+                help: Some("Remove the reference from the footnote definition".to_string()),
+            });
+
+            // Keep the reference as it was written
+            *tree.get_node_mut(node_id).kind_mut() =
+                LetterScriptNodeKind::Text(format!("[^{}]", label));
+            continue;
+        }
+
+        match definitions_by_label.get(label.as_str()) {
+            Some(definition) => {
+                transform_text_tree(
+                    tree,
+                    link_references,
+                    &mut vec![node_id],
+                    definition.text_tree(),
+                );
+
+                let mut labels = enclosing_labels;
+                labels.push(label.clone());
+                let nested_node_ids = tree
+                    .get_node(node_id)
+                    .children()
+                    .iter()
+                    .flat_map(|child_id| find_footnote_nodes(tree, *child_id))
+                    .collect::<Vec<_>>();
+                pending.extend(
+                    nested_node_ids
+                        .into_iter()
+                        .rev()
+                        .map(|nested_node_id| (nested_node_id, labels.clone())),
+                );
+
+                referenced_labels.insert(label);
+            }
+            None => {
+                errors.push(TransformError {
+                    message: format!("Footnote '{}' is not defined", label),
+                    span: node.span().clone(),
+                    help: Some(format!(
+                        "Define the footnote in a separate block like '[^{}]: Footnote text'",
+                        label
+                    )),
+                });
+
+                // Keep the reference as it was written
+                *tree.get_node_mut(node_id).kind_mut() =
+                    LetterScriptNodeKind::Text(format!("[^{}]", label));
+            }
+        }
+    }
+
+    for definition in &definitions {
+        if !referenced_labels.contains(definition.label()) {
+            errors.push(TransformError {
+                message: format!("Footnote '{}' is never referenced", definition.label()),
+                span: definition.span().clone(),
+                help: Some(format!(
+                    "Reference the footnote like '[^{}]' or remove its definition",
+                    definition.label()
+                )),
+            });
+        }
     }
 }
 
+/// Find the footnote nodes below the given node in document order.
+fn find_footnote_nodes(
+    tree: &LetterScriptTree,
+    node_id: LetterScriptNodeId,
+) -> Vec<LetterScriptNodeId> {
+    let node = tree.get_node(node_id);
+    if let LetterScriptNodeKind::Footnote { .. } = node.kind() {
+        return vec![node_id];
+    }
+
+    node.children()
+        .iter()
+        .flat_map(|child_id| find_footnote_nodes(tree, *child_id))
+        .collect()
+}
+
 fn transform_block(
     tree: &mut LetterScriptTree,
+    warnings: &mut Vec<Warning>,
+    link_references: &mut LinkReferences,
     node_stack: &mut Vec<LetterScriptNodeId>,
     block: ParsedBlock,
 ) {
@@ -39,22 +218,27 @@ fn transform_block(
 
     match block.into_kind() {
         ParsedBlockKind::Text(text_block) => {
-            transform_text_block(tree, node_stack, text_block, span)
+            transform_text_block(tree, link_references, node_stack, text_block, span)
         }
         ParsedBlockKind::List(list_block) => {
-            transform_list_block(tree, node_stack, list_block, span)
-        }
-        ParsedBlockKind::Heading(heading_block) => {
-            transform_heading_block(tree, node_stack, heading_block, span)
+            transform_list_block(tree, link_references, node_stack, list_block, span)
         }
+        ParsedBlockKind::Heading(heading_block) => transform_heading_block(
+            tree,
+            warnings,
+            link_references,
+            node_stack,
+            heading_block,
+            span,
+        ),
         ParsedBlockKind::Table(table_block) => {
-            transform_table_block(tree, node_stack, table_block, span)
+            transform_table_block(tree, link_references, node_stack, table_block, span)
         }
         ParsedBlockKind::Image(image_block) => {
-            transform_image_block(tree, node_stack, image_block, span)
+            transform_image_block(tree, link_references, node_stack, image_block, span)
         }
         ParsedBlockKind::Quote(quote_block) => {
-            transform_quote_block(tree, node_stack, quote_block, span)
+            transform_quote_block(tree, link_references, node_stack, quote_block, span)
         }
         ParsedBlockKind::Code(code_block) => {
             transform_code_block(tree, node_stack, code_block, span)
@@ -63,22 +247,37 @@ fn transform_block(
             transform_function_block(tree, node_stack, function_block, span)
         }
         ParsedBlockKind::HorizontalRule => transform_horizontal_rule(tree, node_stack, span),
+        ParsedBlockKind::Footnote(_) => {
+            unreachable!("Footnote definitions are linked after transforming all blocks")
+        }
+        ParsedBlockKind::LinkReference(_) => {
+            unreachable!("Link reference definitions are collected before transforming all blocks")
+        }
     }
 }
 
 fn transform_list_block(
     tree: &mut LetterScriptTree,
+    link_references: &mut LinkReferences,
     node_stack: &mut Vec<LetterScriptNodeId>,
     block: ListBlock,
     span: SourceSpan,
 ) {
     let list_tree = block.into_tree();
     let root = list_tree.root();
-    transform_list_item(tree, node_stack, &list_tree, root.id(), span.clone());
+    transform_list_item(
+        tree,
+        link_references,
+        node_stack,
+        &list_tree,
+        root.id(),
+        span.clone(),
+    );
 }
 
 fn transform_list_item(
     tree: &mut LetterScriptTree,
+    link_references: &mut LinkReferences,
     node_stack: &mut Vec<LetterScriptNodeId>,
     list_tree: &ListTree,
     item_node_id: ListNodeId,
@@ -88,11 +287,7 @@ fn transform_list_item(
 
     match list_node.kind() {
         ListNodeKind::Parent => {
-            let is_ordered = if let ListNodeStyle::Ordered = list_node.style() {
-                true
-            } else {
-                false
-            };
+            let is_ordered = matches!(list_node.style(), ListNodeStyle::Ordered);
 
             let list_node_id = tree.register_node(
                 *node_stack.last().unwrap(),
@@ -105,21 +300,30 @@ fn transform_list_item(
             node_stack.push(list_node_id);
             {
                 for child_id in list_node.children() {
-                    transform_list_item(tree, node_stack, list_tree, *child_id, span.clone());
+                    transform_list_item(
+                        tree,
+                        link_references,
+                        node_stack,
+                        list_tree,
+                        *child_id,
+                        span.clone(),
+                    );
                 }
             }
             node_stack.pop();
         }
-        ListNodeKind::Leaf { text_tree } => {
+        ListNodeKind::Leaf {
+            text_tree, checked, ..
+        } => {
             let list_item_node_id = tree.register_node(
                 *node_stack.last().unwrap(),
-                LetterScriptNodeKind::ListItem,
+                LetterScriptNodeKind::ListItem { checked: *checked },
                 span.clone(),
             );
 
             node_stack.push(list_item_node_id);
             {
-                transform_text_tree(tree, node_stack, text_tree);
+                transform_text_tree(tree, link_references, node_stack, text_tree);
             }
             node_stack.pop();
         }
@@ -128,6 +332,8 @@ fn transform_list_item(
 
 fn transform_heading_block(
     tree: &mut LetterScriptTree,
+    warnings: &mut Vec<Warning>,
+    link_references: &mut LinkReferences,
     node_stack: &mut Vec<LetterScriptNodeId>,
     block: HeadingBlock,
     span: SourceSpan,
@@ -139,6 +345,18 @@ fn transform_heading_block(
         }
     }
 
+    if block.level() > current_level + 1 {
+        warnings.push(Warning::new(
+            WarningCode::HeadingLevelJump,
+            format!(
+                "Heading level jumps from {} to {}",
+                current_level,
+                block.level()
+            ),
+            span.clone(),
+        ));
+    }
+
     if block.level() > current_level {
         for _ in 0..(block.level() - current_level) {
             let section_node_id = tree.register_node(
@@ -163,13 +381,14 @@ fn transform_heading_block(
 
     node_stack.push(heading_node_id);
     {
-        transform_text_tree(tree, node_stack, block.text_tree());
+        transform_text_tree(tree, link_references, node_stack, block.text_tree());
     }
     node_stack.pop();
 }
 
 fn transform_table_block(
     tree: &mut LetterScriptTree,
+    link_references: &mut LinkReferences,
     node_stack: &mut Vec<LetterScriptNodeId>,
     block: TableBlock,
     span: SourceSpan,
@@ -182,11 +401,11 @@ fn transform_table_block(
 
     node_stack.push(table_node_id);
     {
-        transform_table_header_row(tree, node_stack, block.header_row(), span.clone());
+        transform_table_header_row(tree, link_references, node_stack, &block, span.clone());
 
         for row_index in 0..block.row_count() {
             let row = block.get_row(row_index).unwrap();
-            transform_table_row(tree, node_stack, row, span.clone());
+            transform_table_row(tree, link_references, node_stack, &block, row, span.clone());
         }
     }
     node_stack.pop();
@@ -194,7 +413,9 @@ fn transform_table_block(
 
 fn transform_table_row(
     tree: &mut LetterScriptTree,
+    link_references: &mut LinkReferences,
     node_stack: &mut Vec<LetterScriptNodeId>,
+    block: &TableBlock,
     row: &TableRow,
     span: SourceSpan,
 ) {
@@ -206,8 +427,17 @@ fn transform_table_row(
 
     node_stack.push(table_row_node_id);
     {
-        for cell in row {
-            transform_table_cell(tree, node_stack, cell, span.clone());
+        // Pad rows with missing cells and drop extra cells, so that all rows match the header row
+        for column_index in 0..block.column_count() {
+            let alignment = block.alignment(column_index);
+            transform_table_cell(
+                tree,
+                link_references,
+                node_stack,
+                row.get(column_index),
+                alignment,
+                span.clone(),
+            );
         }
     }
     node_stack.pop();
@@ -215,8 +445,9 @@ fn transform_table_row(
 
 fn transform_table_header_row(
     tree: &mut LetterScriptTree,
+    link_references: &mut LinkReferences,
     node_stack: &mut Vec<LetterScriptNodeId>,
-    header_row: &TableRow,
+    block: &TableBlock,
     span: SourceSpan,
 ) {
     let table_header_node_id = tree.register_node(
@@ -227,8 +458,16 @@ fn transform_table_header_row(
 
     node_stack.push(table_header_node_id);
     {
-        for header_cell in header_row {
-            transform_table_cell(tree, node_stack, header_cell, span.clone());
+        for (column_index, header_cell) in block.header_row().iter().enumerate() {
+            let alignment = block.alignment(column_index);
+            transform_table_cell(
+                tree,
+                link_references,
+                node_stack,
+                Some(header_cell),
+                alignment,
+                span.clone(),
+            );
         }
     }
     node_stack.pop();
@@ -236,26 +475,31 @@ fn transform_table_header_row(
 
 fn transform_table_cell(
     tree: &mut LetterScriptTree,
+    link_references: &mut LinkReferences,
     node_stack: &mut Vec<LetterScriptNodeId>,
-    cell: &TableCell,
+    cell: Option<&TableCell>,
+    alignment: Option<Alignment>,
     span: SourceSpan,
 ) {
     let table_cell_node_id = tree.register_node(
         *node_stack.last().unwrap(),
-        LetterScriptNodeKind::TableCell,
+        LetterScriptNodeKind::TableCell { alignment },
         span,
     );
 
-    node_stack.push(table_cell_node_id);
-    {
-        let text_tree = cell.text_tree();
-        transform_text_tree(tree, node_stack, text_tree);
+    if let Some(cell) = cell {
+        node_stack.push(table_cell_node_id);
+        {
+            let text_tree = cell.text_tree();
+            transform_text_tree(tree, link_references, node_stack, text_tree);
+        }
+        node_stack.pop();
     }
-    node_stack.pop();
 }
 
 fn transform_image_block(
     tree: &mut LetterScriptTree,
+    link_references: &mut LinkReferences,
     node_stack: &mut Vec<LetterScriptNodeId>,
     block: ImageBlock,
     span: SourceSpan,
@@ -264,6 +508,7 @@ fn transform_image_block(
         *node_stack.last().unwrap(),
         LetterScriptNodeKind::Image {
             src: block.src().to_string(),
+            title: block.title().map(|title| title.to_string()),
         },
         span,
     );
@@ -271,23 +516,32 @@ fn transform_image_block(
     node_stack.push(node_id);
 
     let text_tree = block.text_tree();
-    transform_text_tree(tree, node_stack, text_tree);
+    transform_text_tree(tree, link_references, node_stack, text_tree);
 
     node_stack.pop();
 }
 
 fn transform_quote_block(
     tree: &mut LetterScriptTree,
+    link_references: &mut LinkReferences,
     node_stack: &mut Vec<LetterScriptNodeId>,
     block: QuoteBlock,
     span: SourceSpan,
 ) {
     let quote_tree = block.into_tree();
-    transform_quote_node(tree, node_stack, &quote_tree, quote_tree.root().id(), &span);
+    transform_quote_node(
+        tree,
+        link_references,
+        node_stack,
+        &quote_tree,
+        quote_tree.root().id(),
+        &span,
+    );
 }
 
 fn transform_quote_node(
     tree: &mut LetterScriptTree,
+    link_references: &mut LinkReferences,
     node_stack: &mut Vec<LetterScriptNodeId>,
     quote_tree: &QuoteTree,
     quote_node_id: QuoteNodeId,
@@ -302,29 +556,40 @@ fn transform_quote_node(
 
             node_stack.push(node_id);
             for child_id in quote_node.children() {
-                transform_quote_node(tree, node_stack, quote_tree, *child_id, span);
+                transform_quote_node(
+                    tree,
+                    link_references,
+                    node_stack,
+                    quote_tree,
+                    *child_id,
+                    span,
+                );
             }
             node_stack.pop();
         }
         QuoteNodeKind::Leaf { text_tree } => {
-            transform_text_tree(tree, node_stack, text_tree);
+            transform_text_tree(tree, link_references, node_stack, text_tree);
         }
     }
 }
 
 fn transform_code_block(
     tree: &mut LetterScriptTree,
-    node_stack: &mut Vec<LetterScriptNodeId>,
+    node_stack: &[LetterScriptNodeId],
     block: CodeBlock,
     span: SourceSpan,
 ) {
     let language = block.language().as_ref().map(|s| s.to_string());
+    let attributes = block.attributes().clone();
     let src = block.src().to_string();
 
     let parent_id = *node_stack.last().unwrap();
     let node_id = tree.register_node(
         parent_id,
-        LetterScriptNodeKind::Code { language },
+        LetterScriptNodeKind::Code {
+            language,
+            attributes,
+        },
         span.clone(),
     );
     tree.register_node(node_id, LetterScriptNodeKind::Text(src), span);
@@ -332,7 +597,7 @@ fn transform_code_block(
 
 fn transform_function_block(
     tree: &mut LetterScriptTree,
-    node_stack: &mut Vec<LetterScriptNodeId>,
+    node_stack: &[LetterScriptNodeId],
     block: FunctionBlock,
     span: SourceSpan,
 ) {
@@ -349,7 +614,7 @@ fn transform_function_block(
 
 fn transform_horizontal_rule(
     tree: &mut LetterScriptTree,
-    node_stack: &mut Vec<LetterScriptNodeId>,
+    node_stack: &[LetterScriptNodeId],
     span: SourceSpan,
 ) {
     let parent_id = *node_stack.last().unwrap();
@@ -358,6 +623,7 @@ fn transform_horizontal_rule(
 
 fn transform_text_block(
     tree: &mut LetterScriptTree,
+    link_references: &mut LinkReferences,
     node_stack: &mut Vec<LetterScriptNodeId>,
     block: TextBlock,
     span: SourceSpan,
@@ -371,24 +637,26 @@ fn transform_text_block(
     node_stack.push(paragraph_node_id);
     {
         let text_tree = block.into_tree();
-        transform_text_tree(tree, node_stack, &text_tree);
+        transform_text_tree(tree, link_references, node_stack, &text_tree);
     }
     node_stack.pop();
 }
 
 fn transform_text_tree(
     tree: &mut LetterScriptTree,
+    link_references: &mut LinkReferences,
     node_stack: &mut Vec<LetterScriptNodeId>,
     text_tree: &TextTree,
 ) {
     let root = text_tree.root();
     for child_id in root.children() {
-        transform_text_node(tree, node_stack, &text_tree, *child_id);
+        transform_text_node(tree, link_references, node_stack, text_tree, *child_id);
     }
 }
 
 fn transform_text_node(
     tree: &mut LetterScriptTree,
+    link_references: &mut LinkReferences,
     node_stack: &mut Vec<LetterScriptNodeId>,
     text_tree: &TextTree,
     text_node_id: TextNodeId,
@@ -401,15 +669,42 @@ fn transform_text_node(
         TextNodeKind::Text { src } => LetterScriptNodeKind::Text(src.clone()),
         TextNodeKind::Bold => LetterScriptNodeKind::Bold,
         TextNodeKind::Italic => LetterScriptNodeKind::Italic,
-        TextNodeKind::Code => LetterScriptNodeKind::Code { language: None },
-        TextNodeKind::Link { target } => LetterScriptNodeKind::Link {
+        TextNodeKind::Strikethrough => LetterScriptNodeKind::Strikethrough,
+        TextNodeKind::LineBreak => LetterScriptNodeKind::LineBreak,
+        TextNodeKind::Code => LetterScriptNodeKind::Code {
+            language: None,
+            attributes: HashMap::new(),
+        },
+        TextNodeKind::Link { target, title } => LetterScriptNodeKind::Link {
             target: target.clone(),
+            title: title.clone(),
+        },
+        TextNodeKind::Image { src, title } => LetterScriptNodeKind::Image {
+            src: src.clone(),
+            title: title.clone(),
         },
-        TextNodeKind::Image { src } => LetterScriptNodeKind::Image { src: src.clone() },
         TextNodeKind::Function { name, parameters } => LetterScriptNodeKind::Function {
             name: name.clone(),
             parameters: parameters.clone(),
         },
+        TextNodeKind::FootnoteReference { label } => LetterScriptNodeKind::Footnote {
+            label: label.clone(),
+        },
+        TextNodeKind::LinkReference { reference, src }
+        | TextNodeKind::ImageReference { reference, src } => {
+            let is_image = matches!(text_node.kind(), TextNodeKind::ImageReference { .. });
+
+            match link_references.resolve(reference, &span) {
+                Some((src, title)) if is_image => LetterScriptNodeKind::Image { src, title },
+                Some((target, title)) => LetterScriptNodeKind::Link { target, title },
+                None => {
+                    // Keep the reference as it was written, as it may just be text in brackets
+                    let text = LetterScriptNodeKind::Text(src.clone());
+                    tree.register_node(parent_id, text, span);
+                    return;
+                }
+            }
+        }
         _ => unreachable!(),
     };
 
@@ -417,7 +712,7 @@ fn transform_text_node(
     node_stack.push(node_id);
 
     for child_id in text_node.children() {
-        transform_text_node(tree, node_stack, text_tree, *child_id);
+        transform_text_node(tree, link_references, node_stack, text_tree, *child_id);
     }
 
     node_stack.pop();
@@ -427,11 +722,12 @@ fn transform_text_node(
 mod tests {
     use crate::categorizer::BlockCategorizer;
     use crate::parser::BlockParser;
+    use crate::render::{LetterScriptRenderer, RenderOptions};
     use crate::splitter::BlockSplitter;
 
     use super::*;
 
-    fn to_letter_script_str(src: &'static str) -> String {
+    fn transform_str(src: &'static str) -> (LetterScriptTree, Vec<Warning>, Vec<TransformError>) {
         let splitter = BlockSplitter::new(Box::new(src.as_bytes()));
         let categorizer = BlockCategorizer::new();
         let parser = BlockParser::new();
@@ -441,8 +737,12 @@ mod tests {
             .map(|block| categorizer.categorize(block))
             .map(|categorized_block| parser.parse(categorized_block).unwrap());
 
-        let letter_script_tree = transform(parsed_block_iterator).unwrap();
-        letter_script_tree.to_string()
+        transform(parsed_block_iterator).into_parts()
+    }
+
+    fn to_letter_script_str(src: &'static str) -> String {
+        let (letter_script_tree, _, _) = transform_str(src);
+        LetterScriptRenderer::new(&letter_script_tree, &RenderOptions::default()).render()
     }
 
     #[test]
@@ -450,9 +750,7 @@ mod tests {
         assert_eq!(
             to_letter_script_str("# This is a heading"),
             "\
-<heading>
-    This is a heading
-</heading>
+<heading>This is a heading</heading>
 "
         );
     }
@@ -474,29 +772,45 @@ Here is some content.
 "
             ),
             "\
-<heading>
-    This is a heading
-</heading>
+<heading>This is a heading</heading>
 <section>
-    <heading>
-        This is a subheading
-    </heading>
-    <paragraph>
-        With some content.
-    </paragraph>
+    <heading>This is a subheading</heading>
+    <paragraph>With some content.</paragraph>
     <section>
-        <heading>
-            This is a subsubheading
-        </heading>
-        <paragraph>
-            Here is some content. 
-        </paragraph>
+        <heading>This is a subsubheading</heading>
+        <paragraph>Here is some content.</paragraph>
     </section>
 </section>
 "
         );
     }
 
+    #[test]
+    fn should_transform_setext_headings_followed_by_text() {
+        assert_eq!(
+            to_letter_script_str(
+                "\
+Single line
+===========
+Text right below.
+
+Multiple
+lines
+-----
+More text.
+"
+            ),
+            "\
+<heading>Single line</heading>
+<paragraph>Text right below.</paragraph>
+<section>
+    <heading>Multiple lines</heading>
+    <paragraph>More text.</paragraph>
+</section>
+"
+        );
+    }
+
     #[test]
     fn should_transform_text() {
         assert_eq!(
@@ -507,27 +821,23 @@ We can also format in *italic* or even both ***bold and italic***.
 "
             ),
             "\
-<paragraph>
-    Hello World, this is 
-    <b>
-        bold text
-    </b>
-    . We can also format in 
-    <i>
-        italic
-    </i>
-     or even both 
-    <i>
-        <b>
-            bold and italic
-        </b>
-    </i>
-    . 
-</paragraph>
+<paragraph>Hello World, this is <b>bold text</b>. We can also format in <i>italic</i> or even both <i><b>bold and italic</b></i>.</paragraph>
 "
         );
     }
 
+    #[test]
+    fn should_not_turn_line_ending_at_end_of_block_into_space() {
+        assert_eq!(
+            to_letter_script_str("para\n"),
+            "<paragraph>para</paragraph>\n"
+        );
+        assert_eq!(
+            to_letter_script_str("# Plain heading\n"),
+            "<heading>Plain heading</heading>\n"
+        );
+    }
+
     #[test]
     fn should_transform_list() {
         assert_eq!(
@@ -545,35 +855,19 @@ We can also format in *italic* or even both ***bold and italic***.
             ),
             "\
 <list>
-    <list-item>
-        A simple list item
-    </list-item>
-    <list-item>
-        And another one
-    </list-item>
+    <list-item>A simple list item</list-item>
+    <list-item>And another one</list-item>
     <list>
-        <list-item>
-            Now we are nested - yeehaw!
-        </list-item>
+        <list-item>Now we are nested - yeehaw!</list-item>
     </list>
-    <list-item>
-        And a third one
-    </list-item>
+    <list-item>And a third one</list-item>
     <list>
-        <list-item>
-            and
-        </list-item>
+        <list-item>and</list-item>
         <list>
-            <list-item>
-                nesting
-            </list-item>
+            <list-item>nesting</list-item>
             <list ordered=\"true\">
-                <list-item>
-                    even
-                </list-item>
-                <list-item>
-                    further
-                </list-item>
+                <list-item>even</list-item>
+                <list-item>further</list-item>
             </list>
         </list>
     </list>
@@ -582,6 +876,18 @@ We can also format in *italic* or even both ***bold and italic***.
         );
     }
 
+    #[test]
+    fn should_strip_indentation_of_list_item_continuation_lines() {
+        assert_eq!(
+            to_letter_script_str("- item\n  continuation\n"),
+            "\
+<list>
+    <list-item>item continuation</list-item>
+</list>
+"
+        );
+    }
+
     #[test]
     fn should_transform_horizontal_rule() {
         assert_eq!(
@@ -595,13 +901,9 @@ This is another paragraph.
 "
             ),
             "\
-<paragraph>
-    This is a paragraph.
-</paragraph>
+<paragraph>This is a paragraph.</paragraph>
 <horizontal-rule/>
-<paragraph>
-    This is another paragraph. 
-</paragraph>
+<paragraph>This is another paragraph.</paragraph>
 "
         );
     }
@@ -623,17 +925,30 @@ This is another paragraph.
 "
             ),
             "\
-<paragraph>
-    This is a paragraph.
-</paragraph>
-<code>
-    This is a code block.
-    
-    console.log('Hello World!');
-</code>
-<paragraph>
-    This is another paragraph. 
-</paragraph>
+<paragraph>This is a paragraph.</paragraph>
+<code>This is a code block.
+
+console.log('Hello World!');</code>
+<paragraph>This is another paragraph.</paragraph>
+"
+        );
+    }
+
+    #[test]
+    fn should_transform_code_block_info_string_attributes() {
+        assert_eq!(
+            to_letter_script_str(
+                "\
+```rust title=\"main.rs\" linenos highlight=\"2-3\"
+fn main() {
+    println!(\"Hello\");
+}
+```"
+            ),
+            "\
+<code language=\"rust\" highlight=\"2-3\" linenos=\"true\" title=\"main.rs\">fn main() {
+    println!(\"Hello\");
+}</code>
 "
         );
     }
@@ -651,14 +966,10 @@ This is a paragraph.
 "
             ),
             "\
-<paragraph>
-    This is a paragraph.
-</paragraph>
+<paragraph>This is a paragraph.</paragraph>
 <quote>
     This is a quote. It can span multiple lines.
-    <quote>
-        And it can be nested.
-    </quote>
+    <quote>And it can be nested.</quote>
 </quote>
 "
         );
@@ -678,28 +989,73 @@ This is a paragraph.
             "\
 <table>
     <table-header-row>
-        <table-cell>
-            Column 1
-        </table-cell>
-        <table-cell>
-            Column 2
-        </table-cell>
+        <table-cell>Column 1</table-cell>
+        <table-cell>Column 2</table-cell>
     </table-header-row>
     <table-row>
-        <table-cell>
-            Cell 1
-        </table-cell>
-        <table-cell>
-            Cell 2
-        </table-cell>
+        <table-cell>Cell 1</table-cell>
+        <table-cell>Cell 2</table-cell>
     </table-row>
     <table-row>
-        <table-cell>
-            Cell 3
-        </table-cell>
-        <table-cell>
-            Cell 4
-        </table-cell>
+        <table-cell>Cell 3</table-cell>
+        <table-cell>Cell 4</table-cell>
+    </table-row>
+</table>
+"
+        );
+    }
+
+    #[test]
+    fn should_transform_table_column_alignments() {
+        assert_eq!(
+            to_letter_script_str(
+                "\
+| Name | Amount | Note |
+| :--- | -----: | :--: |
+| Rent | 800 | monthly |
+"
+            ),
+            "\
+<table>
+    <table-header-row>
+        <table-cell align=\"left\">Name</table-cell>
+        <table-cell align=\"right\">Amount</table-cell>
+        <table-cell align=\"center\">Note</table-cell>
+    </table-header-row>
+    <table-row>
+        <table-cell align=\"left\">Rent</table-cell>
+        <table-cell align=\"right\">800</table-cell>
+        <table-cell align=\"center\">monthly</table-cell>
+    </table-row>
+</table>
+"
+        );
+    }
+
+    #[test]
+    fn should_normalize_table_rows_to_header_columns() {
+        assert_eq!(
+            to_letter_script_str(
+                "\
+Key | Value
+--- | ---
+`a|b` | 1 | extra
+c
+"
+            ),
+            "\
+<table>
+    <table-header-row>
+        <table-cell>Key</table-cell>
+        <table-cell>Value</table-cell>
+    </table-header-row>
+    <table-row>
+        <table-cell><code>a|b</code></table-cell>
+        <table-cell>1</table-cell>
+    </table-row>
+    <table-row>
+        <table-cell>c</table-cell>
+        <table-cell></table-cell>
     </table-row>
 </table>
 "
@@ -717,12 +1073,8 @@ This is a paragraph.
 "
             ),
             "\
-<paragraph>
-    This is a paragraph.
-</paragraph>
-<image src=\"image.png\">
-    This is an image
-</image>
+<paragraph>This is a paragraph.</paragraph>
+<image src=\"image.png\">This is an image</image>
 "
         );
     }
@@ -736,13 +1088,67 @@ This is a [link](https://example.com).
 "
             ),
             "\
-<paragraph>
-    This is a 
-    <link target=\"https://example.com\">
-        link
-    </link>
-    . 
-</paragraph>
+<paragraph>This is a <link target=\"https://example.com\">link</link>.</paragraph>
+"
+        );
+    }
+
+    #[test]
+    fn should_transform_link_and_image_titles() {
+        assert_eq!(
+            to_letter_script_str(
+                "\
+A [link](https://example.com \"Example\") and ![an image](cat.png 'A cat').
+
+![Block image](image.png \"Title\")
+"
+            ),
+            "\
+<paragraph>A <link target=\"https://example.com\" title=\"Example\">link</link> and <image src=\"cat.png\" title=\"A cat\">an image</image>.</paragraph>
+<image src=\"image.png\" title=\"Title\">Block image</image>
+"
+        );
+    }
+
+    #[test]
+    fn should_transform_autolinks() {
+        assert_eq!(
+            to_letter_script_str("Released on <https://example.com>, see www.example.com/notes."),
+            "\
+<paragraph>Released on <link target=\"https://example.com\">https://example.com</link>, see <link target=\"http://www.example.com/notes\">www.example.com/notes</link>.</paragraph>
+"
+        );
+    }
+
+    #[test]
+    fn should_transform_hard_line_breaks() {
+        assert_eq!(
+            to_letter_script_str(
+                "\
+Roses are red,  
+    violets are blue,\\
+sugar is *sweet*
+and so are you.  
+
+> Quoted  
+> text
+"
+            ),
+            "\
+<paragraph>Roses are red,<break/>violets are blue,<break/>sugar is <i>sweet</i> and so are you.</paragraph>
+<quote>Quoted<break/>text</quote>
+"
+        );
+    }
+
+    #[test]
+    fn should_fold_soft_line_breaks_in_list_items() {
+        assert_eq!(
+            to_letter_script_str("- A long\nitem with `inline\ncode`"),
+            "\
+<list>
+    <list-item>A long item with <code>inline code</code></list-item>
+</list>
 "
         );
     }
@@ -760,8 +1166,205 @@ This is a [link](https://example.com).
 "
             ),
             "\
-<image height=\"200px\" src=\"image.png\" width=\"100px\">
-</image>
+<image height=\"200px\" src=\"image.png\" width=\"100px\"></image>
+"
+        );
+    }
+
+    #[test]
+    fn should_warn_about_heading_level_jump() {
+        let (_, warnings, _) = transform_str(
+            "\
+# Heading
+
+### Subsubheading
+",
+        );
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code(), WarningCode::HeadingLevelJump);
+        assert_eq!(
+            warnings[0].to_diagnostic().message(),
+            "Heading level jumps from 1 to 3"
+        );
+    }
+
+    #[test]
+    fn should_not_warn_about_consecutive_heading_levels() {
+        let (_, warnings, _) = transform_str(
+            "\
+# Heading
+
+## Subheading
+
+### Subsubheading
+
+# Another heading
+",
+        );
+
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn should_link_footnotes() {
+        assert_eq!(
+            to_letter_script_str(
+                "\
+Some claim[^1] and *another*[^note].
+
+[^1]: First source.
+[^note]: Second **source**.
+"
+            ),
+            "\
+<paragraph>Some claim<footnote label=\"1\">First source.</footnote> and <i>another</i><footnote label=\"note\">Second <b>source</b>.</footnote>.</paragraph>
+"
+        );
+    }
+
+    #[test]
+    fn should_link_footnotes_referenced_in_footnotes() {
+        let (tree, _, errors) = transform_str(
+            "\
+Some claim[^1].
+
+[^1]: First source, see also[^2].
+[^2]: Second source.
+",
+        );
+
+        assert!(errors.is_empty());
+        assert_eq!(
+            LetterScriptRenderer::new(&tree, &RenderOptions::default()).render(),
+            "<paragraph>Some claim<footnote label=\"1\">First source, see also<footnote label=\"2\">Second source.</footnote>.</footnote>.</paragraph>\n"
+        );
+    }
+
+    #[test]
+    fn should_report_footnote_referencing_itself() {
+        let (tree, _, errors) = transform_str(
+            "\
+Some claim[^1].
+
+[^1]: First source[^2].
+[^2]: Second source[^1].
+",
+        );
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "Footnote '1' references itself");
+        assert_eq!(errors[0].span.start.line, 4);
+        assert_eq!(
+            LetterScriptRenderer::new(&tree, &RenderOptions::default()).render(),
+            "<paragraph>Some claim<footnote label=\"1\">First source<footnote label=\"2\">Second source[^1].</footnote>.</footnote>.</paragraph>\n"
+        );
+    }
+
+    #[test]
+    fn should_report_undefined_footnote() {
+        let (tree, _, errors) = transform_str("Some claim[^missing].");
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "Footnote 'missing' is not defined");
+        assert_eq!(errors[0].span.start.line, 1);
+        assert_eq!(
+            LetterScriptRenderer::new(&tree, &RenderOptions::default()).render(),
+            "<paragraph>Some claim[^missing].</paragraph>\n"
+        );
+    }
+
+    #[test]
+    fn should_report_unused_and_duplicate_footnote_definitions() {
+        let (_, _, errors) = transform_str(
+            "\
+Some claim[^1].
+
+[^1]: First source.
+[^1]: Same label.
+
+[^2]: Never referenced.
+",
+        );
+
+        let messages = errors
+            .iter()
+            .map(|error| (error.message.as_str(), error.span.start.line))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                ("Footnote '1' is defined more than once", 4),
+                ("Footnote '2' is never referenced", 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_resolve_link_references() {
+        assert_eq!(
+            to_letter_script_str(
+                "\
+Read [the docs][Docs] or [docs][] and see ![A cat][cat].
+
+[docs]: https://example.com/docs \"The docs\"
+[cat]: cat.png
+"
+            ),
+            "\
+<paragraph>Read <link target=\"https://example.com/docs\" title=\"The docs\">the docs</link> or <link target=\"https://example.com/docs\" title=\"The docs\">docs</link> and see <image src=\"cat.png\">A cat</image>.</paragraph>
+"
+        );
+    }
+
+    #[test]
+    fn should_keep_undefined_link_reference_as_text_with_warning() {
+        let (tree, warnings, errors) = transform_str("Read [the *docs*][missing] or ![cat][].");
+
+        assert!(errors.is_empty());
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].code(), WarningCode::UndefinedLinkReference);
+        assert_eq!(
+            warnings[0].to_diagnostic().message(),
+            "Link reference 'missing' is not defined and is thus kept as text"
+        );
+        assert_eq!(
+            LetterScriptRenderer::new(&tree, &RenderOptions::default()).render(),
+            "<paragraph>Read [the *docs*][missing] or ![cat][].</paragraph>\n"
+        );
+    }
+
+    #[test]
+    fn should_keep_brackets_in_prose_as_text() {
+        let (tree, _, errors) =
+            transform_str("Access matrix[i][j] here.\n\n[Note]: remember to bring snacks");
+
+        assert!(errors.is_empty());
+        assert_eq!(
+            LetterScriptRenderer::new(&tree, &RenderOptions::default()).render(),
+            "\
+<paragraph>Access matrix[i][j] here.</paragraph>
+<paragraph>[Note]: remember to bring snacks</paragraph>
+"
+        );
+    }
+
+    #[test]
+    fn should_transform_task_list() {
+        assert_eq!(
+            to_letter_script_str(
+                "\
+- [x] Done
+- [ ] Todo
+- Regular item
+"
+            ),
+            "\
+<list>
+    <list-item checked=\"true\">Done</list-item>
+    <list-item checked=\"false\">Todo</list-item>
+    <list-item>Regular item</list-item>
+</list>
 "
         );
     }
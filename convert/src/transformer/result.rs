@@ -1,9 +1,38 @@
-use crate::parser::ParsedBlock;
-
-pub(crate) type TransformResult<T> = Result<T, TransformError>;
+use crate::transformer::tree::LetterScriptTree;
+use crate::util::SourceSpan;
+use crate::warning::Warning;
 
+/// Problem with the parsed blocks that prevents a faithful transformation,
+/// for example a reference to an undefined footnote.
 #[derive(Debug)]
 pub(crate) struct TransformError {
     pub message: String,
-    pub block: ParsedBlock,
+    pub span: SourceSpan,
+    pub help: Option<String>,
+}
+
+/// Result of the transformation.
+/// The tree is always complete, but may only be a best-effort result if there are errors.
+pub(crate) struct Transformed {
+    tree: LetterScriptTree,
+    warnings: Vec<Warning>,
+    errors: Vec<TransformError>,
+}
+
+impl Transformed {
+    pub(crate) fn new(
+        tree: LetterScriptTree,
+        warnings: Vec<Warning>,
+        errors: Vec<TransformError>,
+    ) -> Self {
+        Self {
+            tree,
+            warnings,
+            errors,
+        }
+    }
+
+    pub(crate) fn into_parts(self) -> (LetterScriptTree, Vec<Warning>, Vec<TransformError>) {
+        (self.tree, self.warnings, self.errors)
+    }
 }
@@ -1,13 +1,16 @@
 use std::collections::HashMap;
 
-pub(crate) use crate::transformer::tree::node::{
+pub use crate::transformer::tree::node::{
     LetterScriptNode, LetterScriptNodeId, LetterScriptNodeKind,
 };
 use crate::util::{IdGenerator, SourcePosition, SourceSpan};
 
 mod node;
 
-pub(crate) struct LetterScriptTree {
+/// Tree of the Letter document model.
+/// Nodes are referenced by their identifier, the root node is always present.
+#[derive(Debug)]
+pub struct LetterScriptTree {
     node_lookup: HashMap<LetterScriptNodeId, LetterScriptNode>,
     root_id: LetterScriptNodeId,
     node_id_generator: IdGenerator,
@@ -33,15 +36,38 @@ impl LetterScriptTree {
         }
     }
 
-    pub(crate) fn root_id(&self) -> LetterScriptNodeId {
+    pub fn root_id(&self) -> LetterScriptNodeId {
         self.root_id
     }
 
-    pub(crate) fn get_node(&self, id: LetterScriptNodeId) -> &LetterScriptNode {
+    /// Get the node with the given identifier.
+    /// Panics if there is no such node in the tree.
+    pub fn get_node(&self, id: LetterScriptNodeId) -> &LetterScriptNode {
         self.node_lookup.get(&id).unwrap()
     }
 
-    pub(crate) fn register_node(
+    /// Get the node with the given identifier for modification.
+    /// Panics if there is no such node in the tree.
+    pub fn get_node_mut(&mut self, id: LetterScriptNodeId) -> &mut LetterScriptNode {
+        self.node_lookup.get_mut(&id).unwrap()
+    }
+
+    /// Get the node with the given identifier, if it is part of the tree.
+    pub fn find_node(&self, id: LetterScriptNodeId) -> Option<&LetterScriptNode> {
+        self.node_lookup.get(&id)
+    }
+
+    /// Get the parent of the node with the given identifier.
+    /// Returns `None` for the root node and nodes not part of the tree.
+    pub fn parent_id(&self, id: LetterScriptNodeId) -> Option<LetterScriptNodeId> {
+        self.node_lookup
+            .values()
+            .find(|node| node.children().contains(&id))
+            .map(|node| node.id())
+    }
+
+    /// Append a new node to the children of the given parent node and return its identifier.
+    pub fn register_node(
         &mut self,
         parent_id: LetterScriptNodeId,
         kind: LetterScriptNodeKind,
@@ -58,123 +84,89 @@ impl LetterScriptTree {
         id
     }
 
-    pub fn to_string(&self) -> String {
-        let mut result = String::new();
+    /// Remove the node with the given identifier and all its descendants from the tree.
+    /// The root node cannot be removed.
+    pub fn remove_node(&mut self, id: LetterScriptNodeId) {
+        if id == self.root_id {
+            return;
+        }
 
-        let root_node = self.node_lookup.get(&self.root_id).unwrap();
-        for child_id in root_node.children() {
-            self.stringify_node(*child_id, &mut result, 0);
+        if let Some(parent_id) = self.parent_id(id) {
+            self.get_node_mut(parent_id).unregister_child(id);
         }
 
-        result
+        let mut ids_to_remove = vec![id];
+        while let Some(id) = ids_to_remove.pop() {
+            if let Some(node) = self.node_lookup.remove(&id) {
+                ids_to_remove.extend_from_slice(node.children());
+            }
+        }
     }
+}
 
-    fn stringify_node(&self, node_id: LetterScriptNodeId, result: &mut String, indent: usize) {
-        let node = self.node_lookup.get(&node_id).unwrap();
-        let indent_string = " ".repeat(indent);
+impl Default for LetterScriptTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        self.stringify_node_start(node, result, &indent_string);
+#[cfg(test)]
+mod tests {
+    use crate::render::test_util::span;
+
+    use super::*;
+
+    #[test]
+    fn should_remove_node_with_descendants() {
+        let mut tree = LetterScriptTree::new();
+        let paragraph_id =
+            tree.register_node(tree.root_id(), LetterScriptNodeKind::Paragraph, span());
+        let bold_id = tree.register_node(paragraph_id, LetterScriptNodeKind::Bold, span());
+        let text_id = tree.register_node(
+            bold_id,
+            LetterScriptNodeKind::Text("bold".to_string()),
+            span(),
+        );
 
-        for child_id in node.children() {
-            self.stringify_node(*child_id, result, indent + 4);
-        }
+        tree.remove_node(bold_id);
 
-        self.stringify_node_end(node, result, &indent_string);
+        assert!(tree.get_node(paragraph_id).children().is_empty());
+        assert!(tree.find_node(bold_id).is_none());
+        assert!(tree.find_node(text_id).is_none());
     }
 
-    fn stringify_node_start(&self, node: &LetterScriptNode, result: &mut String, indent_str: &str) {
-        result.push_str(indent_str);
-
-        match node.kind() {
-            LetterScriptNodeKind::Text(text) => {
-                let lines: Vec<&str> = text.split('\n').collect();
-                let line_count = lines.len();
-                for (i, line) in lines.into_iter().enumerate() {
-                    result.push_str(line);
-
-                    let is_last_line = i == line_count - 1;
-                    if !is_last_line {
-                        result.push('\n');
-                        result.push_str(indent_str);
-                    }
-                }
-            }
-            LetterScriptNodeKind::Heading => result.push_str("<heading>"),
-            LetterScriptNodeKind::Paragraph => result.push_str("<paragraph>"),
-            LetterScriptNodeKind::Section => result.push_str("<section>"),
-            LetterScriptNodeKind::Image { src } => {
-                result.push_str(&format!("<image src=\"{}\">", src));
-            }
-            LetterScriptNodeKind::Quote => result.push_str("<quote>"),
-            LetterScriptNodeKind::List { ordered } => {
-                if *ordered {
-                    result.push_str("<list ordered=\"true\">");
-                } else {
-                    result.push_str("<list>");
-                }
-            }
-            LetterScriptNodeKind::ListItem => result.push_str("<list-item>"),
-            LetterScriptNodeKind::HorizontalRule => result.push_str("<horizontal-rule/>"),
-            LetterScriptNodeKind::Link { target } => {
-                result.push_str(&format!("<link target=\"{}\">", target))
-            }
-            LetterScriptNodeKind::Bold => result.push_str("<b>"),
-            LetterScriptNodeKind::Italic => result.push_str("<i>"),
-            LetterScriptNodeKind::Code { language } => {
-                result.push_str("<code");
-
-                if let Some(language) = language {
-                    result.push_str(&format!(" language=\"{}\"", language));
-                }
-
-                result.push_str(">");
-            }
-            LetterScriptNodeKind::Table => result.push_str("<table>"),
-            LetterScriptNodeKind::TableHeaderRow => result.push_str("<table-header-row>"),
-            LetterScriptNodeKind::TableRow => result.push_str("<table-row>"),
-            LetterScriptNodeKind::TableCell => result.push_str("<table-cell>"),
-            LetterScriptNodeKind::Function { name, parameters } => {
-                result.push_str(&format!("<{}", name));
-
-                let mut entries = parameters.iter().collect::<Vec<_>>();
-                entries.sort_by(|(key_a, _), (key_b, _)| key_a.cmp(key_b));
-                for (key, value) in entries {
-                    result.push_str(&format!(" {}=\"{}\"", key, value));
-                }
-
-                result.push_str(">");
-            }
-            _ => {}
-        }
+    #[test]
+    fn should_find_parent() {
+        let mut tree = LetterScriptTree::new();
+        let paragraph_id =
+            tree.register_node(tree.root_id(), LetterScriptNodeKind::Paragraph, span());
 
-        result.push('\n');
+        assert_eq!(tree.parent_id(paragraph_id), Some(tree.root_id()));
+        assert_eq!(tree.parent_id(tree.root_id()), None);
     }
 
-    fn stringify_node_end(&self, node: &LetterScriptNode, result: &mut String, indent_str: &str) {
-        let s = match node.kind() {
-            LetterScriptNodeKind::Heading => "</heading>".to_string(),
-            LetterScriptNodeKind::Paragraph => "</paragraph>".to_string(),
-            LetterScriptNodeKind::Section => "</section>".to_string(),
-            LetterScriptNodeKind::Image { .. } => "</image>".to_string(),
-            LetterScriptNodeKind::Quote => "</quote>".to_string(),
-            LetterScriptNodeKind::List { .. } => "</list>".to_string(),
-            LetterScriptNodeKind::ListItem => "</list-item>".to_string(),
-            LetterScriptNodeKind::Link { .. } => "</link>".to_string(),
-            LetterScriptNodeKind::Bold => "</b>".to_string(),
-            LetterScriptNodeKind::Italic => "</i>".to_string(),
-            LetterScriptNodeKind::Code { .. } => "</code>".to_string(),
-            LetterScriptNodeKind::Table => "</table>".to_string(),
-            LetterScriptNodeKind::TableHeaderRow => "</table-header-row>".to_string(),
-            LetterScriptNodeKind::TableRow => "</table-row>".to_string(),
-            LetterScriptNodeKind::TableCell => "</table-cell>".to_string(),
-            LetterScriptNodeKind::Function { name, .. } => format!("</{}>", name),
-            _ => "".to_string(),
-        };
-
-        if !s.is_empty() {
-            result.push_str(indent_str);
-            result.push_str(&s);
-            result.push('\n');
+    #[test]
+    fn should_modify_node_kind() {
+        let mut tree = LetterScriptTree::new();
+        let link_id = tree.register_node(
+            tree.root_id(),
+            LetterScriptNodeKind::Link {
+                target: "http://example.com".to_string(),
+                title: None,
+            },
+            span(),
+        );
+
+        if let LetterScriptNodeKind::Link { target, .. } = tree.get_node_mut(link_id).kind_mut() {
+            *target = target.replace("http://", "https://");
         }
+
+        assert_eq!(
+            tree.get_node(link_id).kind(),
+            &LetterScriptNodeKind::Link {
+                target: "https://example.com".to_string(),
+                title: None,
+            }
+        );
     }
 }
@@ -1,43 +1,96 @@
 use std::collections::HashMap;
 
-use crate::util::SourceSpan;
+use crate::util::{Alignment, SourceSpan};
 
-pub(crate) type LetterScriptNodeId = usize;
+/// Identifier of a node in a `LetterScriptTree`.
+pub type LetterScriptNodeId = usize;
 
-pub(crate) struct LetterScriptNode {
+/// A single node of the Letter document tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LetterScriptNode {
     id: LetterScriptNodeId,
     kind: LetterScriptNodeKind,
     children: Vec<LetterScriptNodeId>,
     span: SourceSpan,
 }
 
-pub(crate) enum LetterScriptNodeKind {
+/// Kind of a node in the Letter document tree along with its attributes.
+/// Block nodes (headings, paragraphs, lists, ...) contain inline nodes (text, formatting, links, ...).
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum LetterScriptNodeKind {
+    /// Root of the document containing all top-level blocks.
     Root,
+
+    /// Plain text.
     Text(String),
+
+    /// Heading of the surrounding section (or the document if not in a section).
     Heading,
+
     Paragraph,
+
+    /// Section started by a heading, nested by the heading level.
     Section,
+
+    /// Image whose children are the alternative text.
     Image {
         src: String,
+        title: Option<String>,
     },
+
     Quote,
+
+    /// List of list items and nested lists.
     List {
         ordered: bool,
     },
-    ListItem,
+
+    /// List item, which is a task list item if checked is set.
+    ListItem {
+        checked: Option<bool>,
+    },
+
     HorizontalRule,
+
+    /// Hard line break within text.
+    LineBreak,
+
     Link {
         target: String,
+        title: Option<String>,
     },
+
     Bold,
+
     Italic,
+
+    Strikethrough,
+
+    /// Footnote containing the content of its definition, placed where it is referenced.
+    Footnote {
+        label: String,
+    },
+
+    /// Code block or inline code containing the code as text.
+    /// Code blocks may have attributes like a title, line numbers or highlighted lines.
     Code {
         language: Option<String>,
+        attributes: HashMap<String, String>,
     },
+
     Table,
+
     TableHeaderRow,
+
     TableRow,
-    TableCell,
+
+    /// Table cell, aligned as given by the delimiter row of its column.
+    TableCell {
+        alignment: Option<Alignment>,
+    },
+
+    /// A Letter function call (for example `#break` or `#image(src: image.png)`).
     Function {
         name: String,
         parameters: HashMap<String, String>,
@@ -58,23 +111,37 @@ impl LetterScriptNode {
         }
     }
 
-    pub(crate) fn id(&self) -> LetterScriptNodeId {
+    pub fn id(&self) -> LetterScriptNodeId {
         self.id
     }
 
-    pub(crate) fn kind(&self) -> &LetterScriptNodeKind {
+    pub fn kind(&self) -> &LetterScriptNodeKind {
         &self.kind
     }
 
-    pub(crate) fn children(&self) -> &[LetterScriptNodeId] {
+    pub fn kind_mut(&mut self) -> &mut LetterScriptNodeKind {
+        &mut self.kind
+    }
+
+    /// Identifiers of the child nodes in document order.
+    pub fn children(&self) -> &[LetterScriptNodeId] {
         &self.children
     }
 
-    pub(crate) fn span(&self) -> &SourceSpan {
+    /// Span in the Markdown source the node has been created from.
+    pub fn span(&self) -> &SourceSpan {
         &self.span
     }
 
+    pub(crate) fn span_mut(&mut self) -> &mut SourceSpan {
+        &mut self.span
+    }
+
     pub(crate) fn register_child(&mut self, child: LetterScriptNodeId) {
         self.children.push(child);
     }
+
+    pub(crate) fn unregister_child(&mut self, child: LetterScriptNodeId) {
+        self.children.retain(|id| *id != child);
+    }
 }
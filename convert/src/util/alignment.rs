@@ -0,0 +1,18 @@
+/// Horizontal alignment of a table column, set by colons in the delimiter row (`:---`, `:---:` or `---:`).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Alignment {
+    Left,
+    Center,
+    Right,
+}
+
+impl Alignment {
+    /// Name of the alignment as used in attributes (`left`, `center` or `right`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Alignment::Left => "left",
+            Alignment::Center => "center",
+            Alignment::Right => "right",
+        }
+    }
+}
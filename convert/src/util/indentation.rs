@@ -0,0 +1,52 @@
+/// Number of columns a line must be indented by to make it part of an indented code block.
+pub(crate) const CODE_INDENT_WIDTH: usize = 4;
+
+/// Width of the leading whitespace of the given line in columns, counting a tab as four columns.
+pub(crate) fn indentation_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .map(|c| if c == '\t' { CODE_INDENT_WIDTH } else { 1 })
+        .sum()
+}
+
+/// Strip the indentation of an indented code block from the given line.
+pub(crate) fn strip_code_indentation(line: &str) -> &str {
+    if let Some(rest) = line.strip_prefix('\t') {
+        return rest;
+    }
+
+    strip_leading_spaces(line, CODE_INDENT_WIDTH)
+}
+
+/// Strip up to the given number of leading spaces from the given line.
+pub(crate) fn strip_leading_spaces(line: &str, count: usize) -> &str {
+    let spaces = line.chars().take(count).take_while(|c| *c == ' ').count();
+    &line[spaces..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_measure_indentation_width() {
+        assert_eq!(indentation_width("text"), 0);
+        assert_eq!(indentation_width("  text"), 2);
+        assert_eq!(indentation_width("\ttext"), 4);
+        assert_eq!(indentation_width(" \t text"), 6);
+    }
+
+    #[test]
+    fn should_strip_code_indentation() {
+        assert_eq!(strip_code_indentation("      code"), "  code");
+        assert_eq!(strip_code_indentation("\t\tcode"), "\tcode");
+        assert_eq!(strip_code_indentation("  code"), "code");
+    }
+
+    #[test]
+    fn should_strip_leading_spaces() {
+        assert_eq!(strip_leading_spaces("     code", 3), "  code");
+        assert_eq!(strip_leading_spaces(" code", 3), "code");
+        assert_eq!(strip_leading_spaces("\tcode", 3), "\tcode");
+    }
+}
@@ -0,0 +1,56 @@
+/// Split a link destination like `https://example.com "Title"` or `<my page.html>` into target and optional title.
+/// The title may be enclosed in double quotes, single quotes or parentheses.
+/// Returns `None` if the target is followed by anything but a title.
+pub(crate) fn split_link_destination(src: &str) -> Option<(&str, Option<&str>)> {
+    let src = src.trim();
+    let (target, rest) = match src.strip_prefix('<') {
+        Some(src) => src.split_once('>')?,
+        None => src.split_once(char::is_whitespace).unwrap_or((src, "")),
+    };
+
+    let rest = rest.trim();
+    if rest.is_empty() {
+        return Some((target, None));
+    }
+
+    let is_title = [('"', '"'), ('\'', '\''), ('(', ')')]
+        .iter()
+        .any(|(start, end)| rest.len() >= 2 && rest.starts_with(*start) && rest.ends_with(*end));
+
+    if is_title {
+        Some((target, Some(&rest[1..rest.len() - 1])))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_split_link_destination() {
+        assert_eq!(
+            split_link_destination("https://example.com"),
+            Some(("https://example.com", None))
+        );
+        assert_eq!(
+            split_link_destination("https://example.com \"The title\""),
+            Some(("https://example.com", Some("The title")))
+        );
+        assert_eq!(
+            split_link_destination("cat.png 'A cat'"),
+            Some(("cat.png", Some("A cat")))
+        );
+        assert_eq!(
+            split_link_destination("<my page.html> (My page)"),
+            Some(("my page.html", Some("My page")))
+        );
+    }
+
+    #[test]
+    fn should_reject_link_destination_with_trailing_text() {
+        assert_eq!(split_link_destination("https://example.com and more"), None);
+        assert_eq!(split_link_destination("<unclosed"), None);
+    }
+}
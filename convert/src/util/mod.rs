@@ -1,7 +1,19 @@
+pub use alignment::Alignment;
 pub(crate) use id_generator::IdGenerator;
-pub(crate) use source_position::SourcePosition;
-pub(crate) use source_span::SourceSpan;
+pub(crate) use indentation::{
+    indentation_width, strip_code_indentation, strip_leading_spaces, CODE_INDENT_WIDTH,
+};
+pub(crate) use link_destination::split_link_destination;
+pub use source_position::SourcePosition;
+pub use source_span::SourceSpan;
+pub(crate) use table_row::{is_delimiter_cell, split_table_row, TableRowCell};
+pub(crate) use unescape::{decode_entity, is_escapable, unescape, MAX_ENTITY_LENGTH};
 
+mod alignment;
 mod id_generator;
+mod indentation;
+mod link_destination;
 mod source_position;
 mod source_span;
+mod table_row;
+mod unescape;
@@ -1,6 +1,12 @@
-#[derive(Debug, Clone, PartialEq)]
+use serde::Serialize;
+
+/// Position in the Markdown source.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub struct SourcePosition {
+    /// Line number (starting at 1).
     pub line: usize,
+
+    /// Column number (starting at 1).
     pub column: usize,
 }
 
@@ -1,6 +1,9 @@
+use serde::Serialize;
+
 use crate::util::SourcePosition;
 
-#[derive(Debug, PartialEq, Clone)]
+/// Range in the Markdown source.
+#[derive(Debug, PartialEq, Clone, Serialize)]
 pub struct SourceSpan {
     /// Start of the source span (inclusive).
     pub start: SourcePosition,
@@ -13,4 +16,42 @@ impl SourceSpan {
     pub fn new(start: SourcePosition, end: SourcePosition) -> Self {
         Self { start, end }
     }
+
+    /// Limit the span to the given outer span, so that it does not extend past its parent.
+    pub(crate) fn clamped_to(&self, outer: &SourceSpan) -> SourceSpan {
+        let end = self
+            .end
+            .clone()
+            .clamp(outer.start.clone(), outer.end.clone());
+        let start = self.start.clone().clamp(outer.start.clone(), end.clone());
+
+        SourceSpan::new(start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: (usize, usize), end: (usize, usize)) -> SourceSpan {
+        SourceSpan::new(
+            SourcePosition::new(start.0, start.1),
+            SourcePosition::new(end.0, end.1),
+        )
+    }
+
+    #[test]
+    fn should_clamp_span_to_outer_span() {
+        let outer = span((3, 1), (3, 5));
+
+        assert_eq!(
+            span((3, 1), (4, 1)).clamped_to(&outer),
+            span((3, 1), (3, 5))
+        );
+        assert_eq!(
+            span((3, 2), (3, 4)).clamped_to(&outer),
+            span((3, 2), (3, 4))
+        );
+        assert_eq!(span((2, 1), (5, 1)).clamped_to(&outer), outer);
+    }
 }
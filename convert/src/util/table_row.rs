@@ -0,0 +1,191 @@
+/// Cell of a table row as split by `split_table_row`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TableRowCell {
+    value: String,
+    start_column: usize,
+    end_column: usize,
+}
+
+impl TableRowCell {
+    /// Content of the cell without surrounding whitespace.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Column of the first character of the content within the line (starting at 1).
+    pub fn start_column(&self) -> usize {
+        self.start_column
+    }
+
+    /// Column after the last character of the content within the line.
+    pub fn end_column(&self) -> usize {
+        self.end_column
+    }
+}
+
+/// Split a table row like `| a | b |` into its cells.
+/// Leading and trailing pipes are optional, escaped pipes (`\|`) and pipes in inline code do not split cells.
+/// Escaped pipes in inline code are unescaped, as backslashes have no meaning there.
+pub(crate) fn split_table_row(line: &str) -> Vec<TableRowCell> {
+    let chars = line.chars().collect::<Vec<_>>();
+
+    let mut start = chars.iter().take_while(|c| c.is_whitespace()).count();
+    let mut end = chars.len() - chars.iter().rev().take_while(|c| c.is_whitespace()).count();
+    if start < end && chars[start] == '|' {
+        start += 1;
+    }
+    if start < end && chars[end - 1] == '|' && !is_escaped(&chars[start..end - 1]) {
+        end -= 1;
+    }
+
+    let mut cells = Vec::new();
+    let mut value = String::new();
+    let mut cell_start = start;
+    let mut index = start;
+    while index < end {
+        match chars[index] {
+            '\\' if index + 1 < end => {
+                value.push(chars[index]);
+                value.push(chars[index + 1]);
+                index += 2;
+            }
+            '`' => {
+                let length = chars[index..end].iter().take_while(|c| **c == '`').count();
+                let code_end = find_closing_backticks(&chars[index + length..end], length)
+                    .map(|offset| index + 2 * length + offset);
+
+                match code_end {
+                    Some(code_end) => {
+                        value.push_str(
+                            &chars[index..code_end]
+                                .iter()
+                                .collect::<String>()
+                                .replace("\\|", "|"),
+                        );
+                        index = code_end;
+                    }
+                    None => {
+                        value.extend(&chars[index..index + length]);
+                        index += length;
+                    }
+                }
+            }
+            '|' => {
+                cells.push(create_cell(&chars, &value, cell_start, index));
+                value.clear();
+                cell_start = index + 1;
+                index += 1;
+            }
+            c => {
+                value.push(c);
+                index += 1;
+            }
+        }
+    }
+    cells.push(create_cell(&chars, &value, cell_start, end));
+
+    cells
+}
+
+/// Check whether the given cell of a delimiter row is valid, like `---`, `:---`, `:---:` or `---:`.
+pub(crate) fn is_delimiter_cell(value: &str) -> bool {
+    let dashes = value.strip_prefix(':').unwrap_or(value);
+    let dashes = dashes.strip_suffix(':').unwrap_or(dashes);
+
+    !dashes.is_empty() && dashes.chars().all(|c| c == '-')
+}
+
+fn create_cell(chars: &[char], value: &str, start: usize, end: usize) -> TableRowCell {
+    let leading_whitespace = chars[start..end]
+        .iter()
+        .take_while(|c| c.is_whitespace())
+        .count();
+    let trailing_whitespace = chars[start + leading_whitespace..end]
+        .iter()
+        .rev()
+        .take_while(|c| c.is_whitespace())
+        .count();
+
+    TableRowCell {
+        value: value.trim().to_string(),
+        start_column: start + leading_whitespace + 1,
+        end_column: end - trailing_whitespace + 1,
+    }
+}
+
+/// Check whether the character following the given ones is escaped by an odd number of backslashes.
+fn is_escaped(preceding: &[char]) -> bool {
+    preceding.iter().rev().take_while(|c| **c == '\\').count() % 2 == 1
+}
+
+/// Find the offset of the backtick run of exactly the given length closing inline code.
+fn find_closing_backticks(chars: &[char], length: usize) -> Option<usize> {
+    let mut index = 0;
+    while index < chars.len() {
+        let run = chars[index..].iter().take_while(|c| **c == '`').count();
+        if run == length {
+            return Some(index);
+        }
+        index += run.max(1);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(line: &str) -> Vec<String> {
+        split_table_row(line)
+            .iter()
+            .map(|cell| cell.value().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn should_split_row_with_and_without_outer_pipes() {
+        assert_eq!(values("| a | b |"), vec!["a", "b"]);
+        assert_eq!(values("a | b"), vec!["a", "b"]);
+        assert_eq!(values("  | a | b"), vec!["a", "b"]);
+        assert_eq!(values("a |  |"), vec!["a", ""]);
+    }
+
+    #[test]
+    fn should_not_split_at_escaped_pipes() {
+        assert_eq!(values("| a \\| b | c |"), vec!["a \\| b", "c"]);
+        assert_eq!(values("| a \\\\| b |"), vec!["a \\\\", "b"]);
+        assert_eq!(values("| a \\|"), vec!["a \\|"]);
+    }
+
+    #[test]
+    fn should_not_split_at_pipes_in_inline_code() {
+        assert_eq!(values("| `a|b` | c |"), vec!["`a|b`", "c"]);
+        assert_eq!(values("| ``a`|`b`` | c |"), vec!["``a`|`b``", "c"]);
+        assert_eq!(values("| `a\\|b` | c |"), vec!["`a|b`", "c"]);
+        assert_eq!(values("| `a | b |"), vec!["`a", "b"]);
+    }
+
+    #[test]
+    fn should_locate_cell_content() {
+        let cells = split_table_row("| ab |  c|");
+
+        assert_eq!(cells[0].start_column(), 3);
+        assert_eq!(cells[0].end_column(), 5);
+        assert_eq!(cells[1].start_column(), 9);
+        assert_eq!(cells[1].end_column(), 10);
+    }
+
+    #[test]
+    fn should_check_delimiter_cells() {
+        assert!(is_delimiter_cell("---"));
+        assert!(is_delimiter_cell(":-"));
+        assert!(is_delimiter_cell(":---:"));
+        assert!(is_delimiter_cell("-:"));
+        assert!(!is_delimiter_cell(":"));
+        assert!(!is_delimiter_cell("::"));
+        assert!(!is_delimiter_cell("- -"));
+        assert!(!is_delimiter_cell("abc"));
+        assert!(!is_delimiter_cell(""));
+    }
+}
@@ -0,0 +1,195 @@
+//! Backslash escapes and HTML entities in inline Markdown text.
+
+/// Longest entity we decode, like `&#x10FFFF;`, in chars.
+pub(crate) const MAX_ENTITY_LENGTH: usize = 32;
+
+/// Named entities we decode, which are the ones commonly found in documents.
+const NAMED_ENTITIES: &[(&str, char)] = &[
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{a0}'),
+    ("ensp", '\u{2002}'),
+    ("emsp", '\u{2003}'),
+    ("thinsp", '\u{2009}'),
+    ("shy", '\u{ad}'),
+    ("copy", '©'),
+    ("reg", '®'),
+    ("trade", '™'),
+    ("hellip", '…'),
+    ("mdash", '—'),
+    ("ndash", '–'),
+    ("minus", '−'),
+    ("lsquo", '‘'),
+    ("rsquo", '’'),
+    ("sbquo", '‚'),
+    ("ldquo", '“'),
+    ("rdquo", '”'),
+    ("bdquo", '„'),
+    ("laquo", '«'),
+    ("raquo", '»'),
+    ("lsaquo", '‹'),
+    ("rsaquo", '›'),
+    ("bull", '•'),
+    ("middot", '·'),
+    ("dagger", '†'),
+    ("Dagger", '‡'),
+    ("permil", '‰'),
+    ("prime", '′'),
+    ("deg", '°'),
+    ("plusmn", '±'),
+    ("times", '×'),
+    ("divide", '÷'),
+    ("frac12", '½'),
+    ("frac14", '¼'),
+    ("frac34", '¾'),
+    ("sup2", '²'),
+    ("sup3", '³'),
+    ("micro", 'µ'),
+    ("euro", '€'),
+    ("pound", '£'),
+    ("yen", '¥'),
+    ("cent", '¢'),
+    ("sect", '§'),
+    ("para", '¶'),
+    ("larr", '←'),
+    ("rarr", '→'),
+    ("uarr", '↑'),
+    ("darr", '↓'),
+    ("harr", '↔'),
+    ("rArr", '⇒'),
+    ("hArr", '⇔'),
+    ("le", '≤'),
+    ("ge", '≥'),
+    ("ne", '≠'),
+    ("asymp", '≈'),
+    ("infin", '∞'),
+    ("check", '✓'),
+    ("auml", 'ä'),
+    ("ouml", 'ö'),
+    ("uuml", 'ü'),
+    ("Auml", 'Ä'),
+    ("Ouml", 'Ö'),
+    ("Uuml", 'Ü'),
+    ("szlig", 'ß'),
+    ("aacute", 'á'),
+    ("agrave", 'à'),
+    ("acirc", 'â'),
+    ("eacute", 'é'),
+    ("egrave", 'è'),
+    ("ecirc", 'ê'),
+    ("ccedil", 'ç'),
+    ("ntilde", 'ñ'),
+    ("oslash", 'ø'),
+];
+
+/// Whether the given char can be escaped by a backslash, which holds for all ASCII punctuation.
+pub(crate) fn is_escapable(c: char) -> bool {
+    c.is_ascii_punctuation()
+}
+
+/// Decode the entity like `&amp;`, `&#169;` or `&#x2014;` the given source starts with.
+/// Returns the decoded char and the length of the entity in chars.
+pub(crate) fn decode_entity(src: &str) -> Option<(char, usize)> {
+    let (name, _) = src.strip_prefix('&')?.split_once(';')?;
+    let length = name.len() + 2;
+
+    let decoded = match name.strip_prefix('#') {
+        Some(number) => {
+            let code_point = match number.strip_prefix(['x', 'X']) {
+                Some(hex) if (1..=6).contains(&hex.len()) => u32::from_str_radix(hex, 16).ok()?,
+                Some(_) => return None,
+                None if (1..=7).contains(&number.len()) => number.parse::<u32>().ok()?,
+                None => return None,
+            };
+
+            // Invalid code points are replaced as browsers do
+            match code_point {
+                0 => char::REPLACEMENT_CHARACTER,
+                _ => char::from_u32(code_point).unwrap_or(char::REPLACEMENT_CHARACTER),
+            }
+        }
+        None => {
+            NAMED_ENTITIES
+                .iter()
+                .find(|(entity_name, _)| *entity_name == name)?
+                .1
+        }
+    };
+
+    Some((decoded, length))
+}
+
+/// Resolve the backslash escapes and entities of the given text, for example of a link label or target.
+pub(crate) fn unescape(src: &str) -> String {
+    let mut result = String::with_capacity(src.len());
+
+    let mut offset = 0;
+    while let Some(c) = src[offset..].chars().next() {
+        let rest = &src[offset + c.len_utf8()..];
+
+        match c {
+            '\\' if rest.starts_with(is_escapable) => {
+                let escaped = rest.chars().next().unwrap();
+                result.push(escaped);
+                offset += 1 + escaped.len_utf8();
+            }
+            '&' => match decode_entity(&src[offset..]) {
+                Some((decoded, length)) => {
+                    result.push(decoded);
+                    offset += length;
+                }
+                None => {
+                    result.push(c);
+                    offset += 1;
+                }
+            },
+            _ => {
+                result.push(c);
+                offset += c.len_utf8();
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_decode_named_entities() {
+        assert_eq!(decode_entity("&amp; more"), Some(('&', 5)));
+        assert_eq!(decode_entity("&nbsp;"), Some(('\u{a0}', 6)));
+        assert_eq!(decode_entity("&unknown;"), None);
+        assert_eq!(decode_entity("&amp"), None);
+    }
+
+    #[test]
+    fn should_decode_numeric_entities() {
+        assert_eq!(decode_entity("&#169;"), Some(('©', 6)));
+        assert_eq!(decode_entity("&#x2014;"), Some(('—', 8)));
+        assert_eq!(decode_entity("&#X2014;"), Some(('—', 8)));
+        assert_eq!(
+            decode_entity("&#0;"),
+            Some((char::REPLACEMENT_CHARACTER, 4))
+        );
+        assert_eq!(
+            decode_entity("&#xD800;"),
+            Some((char::REPLACEMENT_CHARACTER, 8))
+        );
+        assert_eq!(decode_entity("&#;"), None);
+        assert_eq!(decode_entity("&#12345678;"), None);
+    }
+
+    #[test]
+    fn should_unescape_text() {
+        assert_eq!(
+            unescape(r"a\*b\* \[c\] \\ \a &copy; &#x2014; & &foo;"),
+            "a*b* [c] \\ \\a © — & &foo;"
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Warnings about suspicious but valid Markdown that may not convert as intended.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::diagnostic::Diagnostic;
+use crate::util::SourceSpan;
+
+/// Code identifying the kind of a warning.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WarningCode {
+    /// A block starting with a function call followed by more text, which is thus treated as text.
+    StrayFunctionText,
+
+    /// An image without alternative text.
+    EmptyImageAlt,
+
+    /// A heading more than one level deeper than the previous one, like `###` following `#`.
+    HeadingLevelJump,
+
+    /// A reference-style link or image like `[label][reference]` whose reference is not defined.
+    UndefinedLinkReference,
+}
+
+impl WarningCode {
+    /// All warning codes.
+    pub const ALL: [WarningCode; 4] = [
+        WarningCode::StrayFunctionText,
+        WarningCode::EmptyImageAlt,
+        WarningCode::HeadingLevelJump,
+        WarningCode::UndefinedLinkReference,
+    ];
+
+    /// Name of the warning code as shown in diagnostics and used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            WarningCode::StrayFunctionText => "stray-function-text",
+            WarningCode::EmptyImageAlt => "empty-image-alt",
+            WarningCode::HeadingLevelJump => "heading-level-jump",
+            WarningCode::UndefinedLinkReference => "undefined-link-reference",
+        }
+    }
+}
+
+impl fmt::Display for WarningCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for WarningCode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        WarningCode::ALL
+            .iter()
+            .find(|code| code.name() == s)
+            .copied()
+            .ok_or_else(|| format!("Unknown warning code '{}'", s))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Warning {
+    code: WarningCode,
+    message: String,
+    span: SourceSpan,
+}
+
+impl Warning {
+    pub(crate) fn new(code: WarningCode, message: impl Into<String>, span: SourceSpan) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            span,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn code(&self) -> WarningCode {
+        self.code
+    }
+
+    pub(crate) fn span(&self) -> &SourceSpan {
+        &self.span
+    }
+
+    pub(crate) fn to_diagnostic(&self) -> Diagnostic {
+        Diagnostic::warning(self.message.clone())
+            .with_code(self.code.name())
+            .with_span(self.span.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_warning_code_by_name() {
+        for code in WarningCode::ALL {
+            assert_eq!(code.name().parse::<WarningCode>(), Ok(code));
+        }
+    }
+
+    #[test]
+    fn should_reject_unknown_warning_code() {
+        assert_eq!(
+            "unknown".parse::<WarningCode>(),
+            Err("Unknown warning code 'unknown'".to_string())
+        );
+    }
+}